@@ -1,25 +1,14 @@
 #[macro_use]
 extern crate criterion;
-use criterion::{black_box, BenchmarkId, Criterion};
+use criterion::{black_box, Criterion};
 use sumcheck_mle::{sort_arr, sumcheck_pml,sumcheck_naive};
 use ark_std::{rand::Rng, test_rng};
 use ark_ff::{Field, Zero};
 use ark_poly::{
-    multivariate::{self,SparsePolynomial,SparseTerm, Term},
+    multivariate::{SparsePolynomial,SparseTerm, Term},
     DenseMVPolynomial,
 };
-use ark_ff::{
-	fields::Fp64,
-	fields::{MontBackend, MontConfig},
-	One, PrimeField,
-};
-
-
-#[derive(MontConfig)]
-#[modulus = "97"]
-#[generator = "5"]
-struct FrConfig;
-type Fp97 = Fp64<MontBackend<FrConfig, 1>>;
+use sumcheck_mle::fields::*;
 
 
 fn rand_poly<R: Rng,F:Field>(l: usize, d: usize, rng: &mut R) -> SparsePolynomial<F, SparseTerm> {
@@ -30,14 +19,13 @@ fn rand_poly<R: Rng,F:Field>(l: usize, d: usize, rng: &mut R) -> SparsePolynomia
     random_terms.push((F::rand(rng), SparseTerm::new(vec![])));
     for _ in 1..num_terms {
         let term = (0..l)
-            .map(|i| {
+            .filter_map(|i| {
                 if rng.gen_bool(0.5) {
                     Some((i, rng.gen_range(1..(d + 1))))
                 } else {
                     None
                 }
             })
-            .flatten()
             .collect();
         let coeff = F::rand(rng);
         random_terms.push((coeff, SparseTerm::new(term)));
@@ -53,7 +41,7 @@ fn naive_mul<F:Field>(
         SparsePolynomial::zero()
     } else {
         let mut result_terms = Vec::new();
-        for (cur_coeff, cur_term) in cur.terms().into_iter() {
+        for (cur_coeff, cur_term) in cur.terms().iter() {
             for (other_coeff, other_term) in other.terms().iter() {
                 let mut term:Vec<(usize, usize)> = cur_term.to_vec();
 
@@ -71,18 +59,34 @@ fn naive_mul<F:Field>(
     }
 }
 
-fn prove_sumcheck_pml(c:&mut Criterion){
+fn prove_sumcheck_pml_over<F: Field>(c:&mut Criterion, field: &str){
     let rng = &mut test_rng();
-    let mut p = Vec::new();
-    p.push(rand_poly::<_, Fp97>(2, 1, rng));
-    p.push(rand_poly::<_, Fp97>(2, 1, rng));
+    let p = vec![
+        rand_poly::<_, F>(2, 1, rng),
+        rand_poly::<_, F>(2, 1, rng),
+    ];
 
-    let mut product = naive_mul(&p[0], &p[1]);
+    let product = naive_mul(&p[0], &p[1]);
 
-    c.bench_function("prove sumcheck pml", |b| b.iter(|| sumcheck_pml::prove_bench(black_box(p.clone()))));
-    c.bench_function("prove sumcheck naive", |b| b.iter(|| sumcheck_naive::prove_bench(black_box(product.clone()))));
+    c.bench_function(&format!("prove sumcheck pml/{}", field), |b| b.iter(|| sumcheck_pml::prove_bench(black_box(p.clone()))));
+    c.bench_function(&format!("prove sumcheck naive/{}", field), |b| b.iter(|| sumcheck_naive::prove_bench(black_box(product.clone()))));
+}
+
+fn prove_sumcheck_pml(c:&mut Criterion){
+    prove_sumcheck_pml_over::<Fp97>(c, "fp97");
+    #[cfg(feature = "goldilocks")]
+    prove_sumcheck_pml_over::<Goldilocks>(c, "goldilocks");
+    #[cfg(feature = "mersenne31")]
+    prove_sumcheck_pml_over::<M31>(c, "m31");
+    #[cfg(feature = "mersenne31")]
+    prove_sumcheck_pml_over::<CM31>(c, "cm31");
+    #[cfg(feature = "bn254")]
+    prove_sumcheck_pml_over::<Bn254Fr>(c, "bn254");
+    #[cfg(feature = "bls12-381")]
+    prove_sumcheck_pml_over::<Bls12_381Fr>(c, "bls12-381");
 }
 
+#[allow(dead_code)]
 fn sort_bench(c:&mut Criterion){
     let mut arr = black_box([6,2,4,1,-9,5]);
 
@@ -0,0 +1,99 @@
+//! Runs a prover and a verifier as separate processes.
+//!
+//! Over TCP, in two terminals:
+//!     cargo run --example interactive -- verify ml tcp 127.0.0.1:7878 "24*x0 + 15*x0*x1 + 35*x1"
+//!     cargo run --example interactive -- prove ml tcp 127.0.0.1:7878 "24*x0 + 15*x0*x1 + 35*x1"
+//!
+//! Over pipes, the verifier spawns the prover as a child process talking on stdin/stdout:
+//!     cargo run --example interactive -- verify pml spawn "(x0 + 2*x1) * (3*x0*x1 + 5)"
+use std::net::TcpListener;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use rand::{rngs::StdRng, SeedableRng};
+use sumcheck_mle::fields::Goldilocks as GL64;
+use sumcheck_mle::parser::{parse_factors, parse_polynomial};
+use sumcheck_mle::soundness::SecurityConfig;
+use sumcheck_mle::transport::channel::Channel;
+use sumcheck_mle::transport::interactive::*;
+use sumcheck_mle::transport::{PipeTransport, ProtocolError, TcpTransport, Transport};
+use sumcheck_mle::{sumcheck_ml, sumcheck_naive, sumcheck_pml};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Goldilocks gives about 60 bits for small polynomials, short of the 128 bits verifiers
+/// require by default.
+const SECURITY: SecurityConfig = SecurityConfig { min_bits: 50.0, allow_insecure: false };
+
+fn prove<T: Transport>(protocol: &str, poly: &str, transport: T) -> Result<bool, ProtocolError<GL64>> {
+    let mut channel = Channel::new(transport);
+    channel.set_timeout(Some(TIMEOUT))?;
+    match protocol {
+        "ml" => prove_ml(&mut channel, &mut sumcheck_ml::prover::Prover::new(parse_polynomial::<GL64>(poly).unwrap())?),
+        "pml" => prove_pml(&mut channel, &mut sumcheck_pml::prover::Prover::new(parse_factors::<GL64>(poly).unwrap())?),
+        "naive" => prove_naive(&mut channel, &mut sumcheck_naive::Prover::new(parse_polynomial::<GL64>(poly).unwrap())?),
+        _ => usage(),
+    }
+}
+
+fn verify<T: Transport>(protocol: &str, poly: &str, transport: T) -> Result<bool, ProtocolError<GL64>> {
+    let mut channel = Channel::new(transport);
+    channel.set_timeout(Some(TIMEOUT))?;
+    // a seed the prover could guess would let it precompute every challenge
+    let rng = &mut StdRng::from_entropy();
+    match protocol {
+        "ml" => {
+            let options = sumcheck_ml::verifier::VerifierOptions::default().security(SECURITY);
+            verify_ml(&mut channel, parse_polynomial::<GL64>(poly).unwrap(), options, rng)
+        }
+        "pml" => {
+            let options = sumcheck_pml::verifier::VerifierOptions::default().security(SECURITY);
+            verify_pml(&mut channel, parse_factors::<GL64>(poly).unwrap(), options, rng)
+        }
+        "naive" => {
+            let options = sumcheck_naive::VerifierOptions::default().security(SECURITY);
+            verify_naive(&mut channel, parse_polynomial::<GL64>(poly).unwrap(), options, rng)
+        }
+        _ => usage(),
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("usage: interactive <prove|verify> <ml|pml|naive> <tcp ADDR|stdio|spawn> POLY");
+    std::process::exit(2)
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["prove", protocol, "tcp", addr, poly] => prove(protocol, poly, TcpTransport::connect(addr).unwrap()),
+        ["prove", protocol, "stdio", poly] => prove(protocol, poly, PipeTransport::stdio()),
+        ["verify", protocol, "tcp", addr, poly] => {
+            let listener = TcpListener::bind(addr).unwrap();
+            verify(protocol, poly, TcpTransport::accept(&listener).unwrap())
+        }
+        ["verify", protocol, "spawn", poly] => {
+            let mut child = Command::new(std::env::current_exe().unwrap())
+                .args(["prove", protocol, "stdio", poly])
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()
+                .unwrap();
+            let transport = PipeTransport::new(child.stdout.take().unwrap(), child.stdin.take().unwrap());
+            let result = verify(protocol, poly, transport);
+            let _ = child.wait();
+            result
+        }
+        _ => usage(),
+    };
+
+    match result {
+        Ok(accept) => eprintln!("{} {}", args[0], if accept { "accepted" } else { "rejected" }),
+        Err(e) => {
+            eprintln!("{} failed: {}", args[0], e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! Dishonest provers for testing the soundness of the verifiers.
+//!
+//! Every adversary wraps an honest prover and plugs into the same [`SumcheckProver`]
+//! round interface, so it can be run against the matching `Verifier` unchanged. Apart from
+//! [`WrongDegree`] the tampering keeps every round consistent with the previous one, so the
+//! verifier is only saved by the randomness of its challenges.
+#[cfg(test)]
+mod test;
+
+use ark_ff::Field;
+use ark_poly::{univariate, Polynomial};
+
+use crate::error::Error;
+use crate::protocol::SumcheckProver;
+use crate::round_poly::interpolate_at;
+
+/// Round messages an adversary can modify.
+pub trait Tamper<F: Field>: Clone {
+    /// Adds the linear polynomial $a + bX$ to the round polynomial.
+    fn add_linear(&mut self, a: F, b: F);
+
+    /// Evaluates the round polynomial at $x$.
+    fn evaluate(&self, x: F) -> F;
+}
+
+/// Round messages that can carry a polynomial of higher degree than the honest one.
+pub trait RaiseDegree<F: Field>: Tamper<F> {
+    /// Adds $\delta (X^{d+1} - X)$, where $d$ is the current degree of the round polynomial.
+    /// The sum over $\{0, 1\}$ is unchanged.
+    fn raise_degree(&mut self, delta: F);
+}
+
+impl<F: Field> Tamper<F> for (F, F) {
+    fn add_linear(&mut self, a: F, b: F) {
+        self.0 += a;
+        self.1 += a + b;
+    }
+
+    fn evaluate(&self, x: F) -> F {
+        self.0 + x * (self.1 - self.0)
+    }
+}
+
+impl<F: Field> Tamper<F> for Vec<F> {
+    fn add_linear(&mut self, a: F, b: F) {
+        for (t, p) in self.iter_mut().enumerate() {
+            *p += a + b * F::from(t as u64);
+        }
+    }
+
+    fn evaluate(&self, x: F) -> F {
+        interpolate_at(self, x)
+    }
+}
+
+impl<F: Field> RaiseDegree<F> for Vec<F> {
+    fn raise_degree(&mut self, delta: F) {
+        let d = self.len() - 1;
+        self.push(interpolate_at(self, F::from((d + 1) as u64)));
+        for (t, p) in self.iter_mut().enumerate() {
+            let t = F::from(t as u64);
+            *p += delta * (t.pow([(d + 1) as u64]) - t);
+        }
+    }
+}
+
+impl<F: Field> Tamper<F> for univariate::SparsePolynomial<F> {
+    fn add_linear(&mut self, a: F, b: F) {
+        *self += &univariate::SparsePolynomial::from_coefficients_vec(vec![(0, a), (1, b)]);
+    }
+
+    fn evaluate(&self, x: F) -> F {
+        Polynomial::evaluate(self, &x)
+    }
+}
+
+impl<F: Field> RaiseDegree<F> for univariate::SparsePolynomial<F> {
+    fn raise_degree(&mut self, delta: F) {
+        let d = Polynomial::degree(self);
+        *self += &univariate::SparsePolynomial::from_coefficients_vec(vec![(1, -delta), (d + 1, delta)]);
+    }
+}
+
+/// Claims `honest + delta` and shifts the first message by $\delta (1 - X)$ so that it
+/// sums to the false claim. All later rounds are honest.
+pub struct WrongClaim<F: Field, P: SumcheckProver<F>> {
+    inner: P,
+    delta: F,
+}
+
+impl<F: Field, P: SumcheckProver<F>> WrongClaim<F, P> {
+    pub fn new(inner: P, delta: F) -> Self {
+        Self { inner, delta }
+    }
+}
+
+impl<F: Field, P: SumcheckProver<F>> SumcheckProver<F> for WrongClaim<F, P>
+where
+    P::Message: Tamper<F>,
+{
+    type Message = P::Message;
+
+    fn claim(&self) -> F { self.inner.claim() + self.delta }
+
+    fn num_vars(&self) -> usize { self.inner.num_vars() }
+
+    fn rounds(&self) -> usize { self.inner.rounds() }
+
+    fn round(&mut self, r_prev: F, j: usize) -> Result<P::Message, Error<F>> {
+        let mut p = self.inner.round(r_prev, j)?;
+        if j == 0 {
+            p.add_linear(self.delta, -self.delta);
+        }
+        Ok(p)
+    }
+}
+
+/// Claims the honest sum but adds $\delta (1 - 2X)$ to the message of one round, which
+/// keeps that round consistent while changing the polynomial.
+pub struct TamperedRound<F: Field, P: SumcheckProver<F>> {
+    inner: P,
+    round: usize,
+    delta: F,
+}
+
+impl<F: Field, P: SumcheckProver<F>> TamperedRound<F, P> {
+    pub fn new(inner: P, round: usize, delta: F) -> Self {
+        Self { inner, round, delta }
+    }
+}
+
+/// Tampers only with the last round, which is caught by nothing but the final oracle check.
+/// Fails for a prover without rounds.
+pub fn inconsistent_last_round<F: Field, P: SumcheckProver<F>>(inner: P, delta: F) -> Result<TamperedRound<F, P>, Error<F>> {
+    let round = inner.num_vars().checked_sub(1).ok_or(Error::EmptyInput)?;
+    Ok(TamperedRound::new(inner, round, delta))
+}
+
+impl<F: Field, P: SumcheckProver<F>> SumcheckProver<F> for TamperedRound<F, P>
+where
+    P::Message: Tamper<F>,
+{
+    type Message = P::Message;
+
+    fn claim(&self) -> F { self.inner.claim() }
+
+    fn num_vars(&self) -> usize { self.inner.num_vars() }
+
+    fn rounds(&self) -> usize { self.inner.rounds() }
+
+    fn round(&mut self, r_prev: F, j: usize) -> Result<P::Message, Error<F>> {
+        let mut p = self.inner.round(r_prev, j)?;
+        if j == self.round {
+            p.add_linear(self.delta, -self.delta.double());
+        }
+        Ok(p)
+    }
+}
+
+/// Sends a round polynomial of one degree more than the honest one in one round.
+pub struct WrongDegree<F: Field, P: SumcheckProver<F>> {
+    inner: P,
+    round: usize,
+    delta: F,
+}
+
+impl<F: Field, P: SumcheckProver<F>> WrongDegree<F, P> {
+    pub fn new(inner: P, round: usize, delta: F) -> Self {
+        Self { inner, round, delta }
+    }
+}
+
+impl<F: Field, P: SumcheckProver<F>> SumcheckProver<F> for WrongDegree<F, P>
+where
+    P::Message: RaiseDegree<F>,
+{
+    type Message = P::Message;
+
+    fn claim(&self) -> F { self.inner.claim() }
+
+    fn num_vars(&self) -> usize { self.inner.num_vars() }
+
+    fn rounds(&self) -> usize { self.inner.rounds() }
+
+    fn round(&mut self, r_prev: F, j: usize) -> Result<P::Message, Error<F>> {
+        let mut p = self.inner.round(r_prev, j)?;
+        if j == self.round {
+            p.raise_degree(self.delta);
+        }
+        Ok(p)
+    }
+}
+
+/// Claims `honest + delta` betting that the verifier's challenges repeat those of an
+/// earlier run.
+///
+/// In round $j$ the honest message is shifted by $c (X - \hat{r}_j)$, where $\hat{r}_j$ is the
+/// replayed challenge and $c$ makes the message sum to what the verifier expects. The shift
+/// vanishes at $\hat{r}_j$, so as soon as a challenge matches its replay the false claim
+/// collapses onto the honest one and the prover passes. Against fresh challenges it
+/// wins with probability about $n/|F|$.
+pub struct ReplayedChallenges<F: Field, P: SumcheckProver<F>> {
+    inner: P,
+    delta: F,
+    replayed: Vec<F>,
+    expected: F,
+    last: Option<P::Message>,
+}
+
+impl<F: Field, P: SumcheckProver<F>> ReplayedChallenges<F, P> {
+    pub fn new(inner: P, delta: F, replayed: Vec<F>) -> Self {
+        let expected = inner.claim() + delta;
+        Self {
+            inner,
+            delta,
+            replayed,
+            expected,
+            last: None,
+        }
+    }
+}
+
+impl<F: Field, P: SumcheckProver<F>> SumcheckProver<F> for ReplayedChallenges<F, P>
+where
+    P::Message: Tamper<F>,
+{
+    type Message = P::Message;
+
+    fn claim(&self) -> F { self.inner.claim() + self.delta }
+
+    fn num_vars(&self) -> usize { self.inner.num_vars() }
+
+    fn rounds(&self) -> usize { self.inner.rounds() }
+
+    fn round(&mut self, r_prev: F, j: usize) -> Result<P::Message, Error<F>> {
+        if let Some(last) = &self.last {
+            self.expected = last.evaluate(r_prev);
+        }
+
+        let mut p = self.inner.round(r_prev, j)?;
+        let debt = self.expected - p.evaluate(F::zero()) - p.evaluate(F::one());
+        if let (false, Some(r_hat)) = (debt.is_zero(), self.replayed.get(j)) {
+            if let Some(inv) = (F::one() - r_hat.double()).inverse() {
+                let c = debt * inv;
+                p.add_linear(-c * r_hat, c);
+            }
+        }
+
+        self.last = Some(p.clone());
+        Ok(p)
+    }
+}
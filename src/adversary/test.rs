@@ -0,0 +1,141 @@
+use ark_ff::{Field, UniformRand};
+use ark_poly::multivariate::{SparsePolynomial, SparseTerm};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+use crate::adversary::*;
+use crate::fields::Fp97;
+use crate::oracle::Product;
+use crate::parser::{parse_factors, parse_polynomial};
+use crate::error::Error;
+use crate::protocol::{run_protocol, SumcheckProver, SumcheckVerifier};
+use crate::{sumcheck_ml, sumcheck_naive, sumcheck_pml};
+
+type Poly = SparsePolynomial<Fp97, SparseTerm>;
+
+const TRIALS: u64 = 500;
+
+fn delta(seed: u64) -> Fp97 {
+    Fp97::from(seed % 96 + 1)
+}
+
+/// The challenges a verifier seeded with `seed` is going to draw.
+fn replayed(seed: u64, n: usize) -> Vec<Fp97> {
+    let rng = &mut StdRng::seed_from_u64(seed);
+    (0..n).map(|_| Fp97::rand(rng)).collect()
+}
+
+/// Seeds whose first challenge is not $1/2$, the one point where the first message
+/// cannot be shifted.
+fn replayable(n: usize) -> impl Iterator<Item = u64> {
+    let half = Fp97::from(2u64).inverse().unwrap();
+    (0..TRIALS).filter(move |s| replayed(*s, n)[0] != half)
+}
+
+/// Asserts the empirical acceptance rate stays under the $d \cdot n / |F|$ bound.
+fn assert_sound(name: &str, accepts: impl Fn(u64) -> bool, d: usize, n: usize) {
+    let accepted = (0..TRIALS).filter(|seed| accepts(*seed)).count();
+    let rate = accepted as f64 / TRIALS as f64;
+    let bound = (d * n) as f64 / 97.0;
+    assert!(rate <= bound, "{}: accepted {}/{} times, bound {:.4}", name, accepted, TRIALS, bound);
+}
+
+fn accepts<P, V>(prover: P, verifier: impl FnOnce(Fp97) -> V, seed: u64) -> bool
+where
+    P: SumcheckProver<Fp97>,
+    V: SumcheckVerifier<Fp97, Message = P::Message, Error = Error<Fp97>>,
+{
+    let rng = &mut StdRng::seed_from_u64(seed);
+    run_protocol(prover, verifier, rng).is_ok_and(|outcome| outcome.accepted)
+}
+
+fn ml_accepts<P: SumcheckProver<Fp97, Message = (Fp97, Fp97)>>(prover: P, g: &Poly, seed: u64) -> bool {
+    accepts(prover, |claim| sumcheck_ml::verifier::Verifier::with_options(g.clone(), claim, sumcheck_ml::verifier::VerifierOptions::default().allow_insecure()).unwrap(), seed)
+}
+
+fn pml_accepts<P: SumcheckProver<Fp97, Message = Vec<Fp97>>>(prover: P, g: &[Poly], seed: u64) -> bool {
+    accepts(prover, |claim| sumcheck_pml::verifier::Verifier::with_options(Product::new(g.to_vec()).unwrap(), claim, sumcheck_pml::verifier::VerifierOptions::default().allow_insecure()).unwrap(), seed)
+}
+
+fn naive_accepts<P>(prover: P, g: &Poly, seed: u64) -> bool
+where
+    P: SumcheckProver<Fp97, Message = ark_poly::univariate::SparsePolynomial<Fp97>>,
+{
+    let verifier = |claim| {
+        let mut verifier = sumcheck_naive::Verifier::with_options(g.clone(), sumcheck_naive::VerifierOptions::default().allow_insecure()).unwrap();
+        verifier.set_c_1(claim);
+        verifier
+    };
+    accepts(prover, verifier, seed)
+}
+
+#[test]
+fn test_ml_soundness(){
+    let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1 + 35*x1 + 3*x2 + 7*x0*x2").unwrap();
+    let honest = || sumcheck_ml::prover::Prover::new(g.clone()).unwrap();
+    let (d, n) = (1, 3);
+
+    assert!(ml_accepts(honest(), &g, 0));
+    assert_sound("wrong claim", |s| ml_accepts(WrongClaim::new(honest(), delta(s)), &g, s), d, n);
+    for round in 0..n {
+        assert_sound("tampered round", |s| ml_accepts(TamperedRound::new(honest(), round, delta(s)), &g, s), d, n);
+    }
+    assert_sound("last round", |s| ml_accepts(inconsistent_last_round(honest(), delta(s)).unwrap(), &g, s), d, n);
+    assert_sound("replay", |s| ml_accepts(ReplayedChallenges::new(honest(), delta(s), replayed(s + 1, n)), &g, s), d, n);
+
+    // replaying the verifier's own challenges breaks soundness completely
+    assert!(replayable(n).all(|s| ml_accepts(ReplayedChallenges::new(honest(), delta(s), replayed(s, n)), &g, s)));
+}
+
+#[test]
+fn test_pml_soundness(){
+    let g = parse_factors::<Fp97>("(24*x0 + 15*x0*x1 + 35*x1 + x2) * (17*x0 + 30*x0*x1 + 33*x1 + 5*x2)").unwrap();
+    let honest = || sumcheck_pml::prover::Prover::new(g.clone()).unwrap();
+    let (d, n) = (2, 3);
+
+    assert!(pml_accepts(honest(), &g, 0));
+    assert_sound("wrong claim", |s| pml_accepts(WrongClaim::new(honest(), delta(s)), &g, s), d, n);
+    for round in 0..n {
+        assert_sound("tampered round", |s| pml_accepts(TamperedRound::new(honest(), round, delta(s)), &g, s), d, n);
+        assert_sound("wrong degree", |s| pml_accepts(WrongDegree::new(honest(), round, delta(s)), &g, s), d, n);
+    }
+    assert_sound("last round", |s| pml_accepts(inconsistent_last_round(honest(), delta(s)).unwrap(), &g, s), d, n);
+    assert_sound("replay", |s| pml_accepts(ReplayedChallenges::new(honest(), delta(s), replayed(s + 1, n)), &g, s), d, n);
+
+    assert!(replayable(n).all(|s| pml_accepts(ReplayedChallenges::new(honest(), delta(s), replayed(s, n)), &g, s)));
+}
+
+#[test]
+fn test_naive_soundness(){
+    let g = parse_polynomial::<Fp97>("20*x0^2 + 5*x0^2*x1 + 29*x0*x1 + 62*x0^2*x1^2 + 90*x0*x1^2 + 88*x1^2 + 3*x2").unwrap();
+    let honest = || sumcheck_naive::Prover::new(g.clone()).unwrap();
+    let (d, n) = (2, 3);
+
+    assert!(naive_accepts(honest(), &g, 0));
+    assert_sound("wrong claim", |s| naive_accepts(WrongClaim::new(honest(), delta(s)), &g, s), d, n);
+    for round in 0..n {
+        assert_sound("tampered round", |s| naive_accepts(TamperedRound::new(honest(), round, delta(s)), &g, s), d, n);
+        assert_sound("wrong degree", |s| naive_accepts(WrongDegree::new(honest(), round, delta(s)), &g, s), d, n);
+    }
+    assert_sound("last round", |s| naive_accepts(inconsistent_last_round(honest(), delta(s)).unwrap(), &g, s), d, n);
+    assert_sound("replay", |s| naive_accepts(ReplayedChallenges::new(honest(), delta(s), replayed(s + 1, n)), &g, s), d, n);
+
+    assert!(replayable(n).all(|s| naive_accepts(ReplayedChallenges::new(honest(), delta(s), replayed(s, n)), &g, s)));
+}
+
+/// A prover without rounds, which none of the real provers can be built as.
+struct NoRounds;
+
+impl SumcheckProver<Fp97> for NoRounds {
+    type Message = (Fp97, Fp97);
+
+    fn claim(&self) -> Fp97 { Fp97::from(0u8) }
+
+    fn num_vars(&self) -> usize { 0 }
+
+    fn round(&mut self, _: Fp97, j: usize) -> Result<(Fp97, Fp97), Error<Fp97>> { Err(Error::RoundOutOfOrder(j, 0)) }
+}
+
+#[test]
+fn test_no_rounds(){
+    assert!(matches!(inconsistent_last_round(NoRounds, delta(0)), Err(Error::EmptyInput)));
+}
@@ -0,0 +1,83 @@
+use ark_ff::Field;
+
+/// An error type of the sum check protocols, shared by the provers and verifiers of
+/// [`crate::sumcheck_ml`], [`crate::sumcheck_pml`] and [`crate::sumcheck_naive`].
+///
+/// Rounds are counted from 0.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum Error<F: Field> {
+    #[error("round {0}: prover message sums to {2}, expected {1}")]
+    ClaimMismatch(usize, F, F),
+
+    #[error("claimed sum {0} differs from the sum {1} over the hypercube")]
+    FalseClaim(F, F),
+
+    #[error("round {0}: message has {1} values, expected {2}")]
+    WrongMessageLength(usize, usize, usize),
+
+    #[error("round {0}: polynomial has degree {1}, exceeding the bound {2}")]
+    DegreeTooHigh(usize, usize, usize),
+
+    #[error("round {0} is out of order, the protocol has {1} rounds")]
+    RoundOutOfOrder(usize, usize),
+
+    #[error("cannot stop after {0} rounds of a protocol with {1} rounds")]
+    InvalidRoundCount(usize, usize),
+
+    #[error("proof has {1} rounds, expected {0}")]
+    WrongProofLength(usize, usize),
+
+    #[error("oracle evaluates to {1} at the final point, expected {0}")]
+    OracleMismatch(F, F),
+
+    #[error("oracle answered {1} of {0} points")]
+    WrongOracleAnswerCount(usize, usize),
+
+    #[error("polynomial has {1} variables, expected {0}")]
+    NumVarsMismatch(usize, usize),
+
+    #[error("polynomial has degree {1} in x{0}, the protocol needs a multilinear one")]
+    NotMultilinear(usize, usize),
+
+    #[error("table has {1} entries, expected {0}")]
+    WrongTableLength(usize, usize),
+
+    #[error("empty input: no polynomial or no variables")]
+    EmptyInput,
+
+    #[error("field has fewer than the {0} distinct evaluation points the protocol needs")]
+    FieldTooSmall(usize),
+
+    #[error("opening of the commitment at the final point does not verify")]
+    InvalidOpening,
+
+    #[error("soundness of {0:.1} bits is below the required {1:.1} bits")]
+    InsufficientSecurity(f64, f64),
+
+    #[error("repetition {0} claims {2}, the first one claims {1}")]
+    RepetitionClaimMismatch(usize, F, F),
+
+    #[error("got {1} values for {0} repetitions")]
+    WrongRepetitionCount(usize, usize),
+}
+
+/// Fails with [`Error::FieldTooSmall`] unless $0, 1, ..., n-1$ are distinct in `F`.
+pub(crate) fn check_evaluation_points<F: Field>(n: usize) -> Result<(), Error<F>> {
+    // the characteristic is at least 2, so 0 and 1 always fit
+    let characteristic = F::characteristic();
+    let small = characteristic.iter().skip(1).all(|limb| *limb == 0);
+    if small && characteristic[0] < n as u64 {
+        return Err(Error::FieldTooSmall(n));
+    }
+    Ok(())
+}
+
+/// Fails with [`Error::RoundOutOfOrder`] unless a prover that has received the challenges
+/// `r` may play round `j` of `rounds`. Round 0 uses no challenge, so it may be repeated.
+pub(crate) fn check_prover_round<F: Field>(j: usize, r: &[F], rounds: usize) -> Result<(), Error<F>> {
+    let next = j == r.len() + 1 || (j == 0 && r.is_empty());
+    if !next || j >= rounds {
+        return Err(Error::RoundOutOfOrder(j, rounds));
+    }
+    Ok(())
+}
@@ -0,0 +1,177 @@
+//! Non-interactive sumcheck via the Fiat-Shamir transform.
+//!
+//! Instead of a verifier sending random challenges, both sides derive every challenge by
+//! hashing everything said so far: the number of variables, the claim and the round
+//! polynomials up to that round. A prover then produces the whole [`Proof`] on its own and
+//! anyone can check it later in one go, with the `verify` functions of
+//! [`crate::sumcheck_ml`], [`crate::sumcheck_pml`] and [`crate::sumcheck_naive`].
+//!
+//! The transcript does not bind the polynomial $g$, which has to be fixed before the claim
+//! is made, e.g. by a commitment.
+#[cfg(test)]
+mod test;
+
+use ark_ff::Field;
+use sha2::{Digest, Sha256};
+
+use crate::error::Error;
+use crate::protocol::SumcheckProver;
+use crate::round_poly::RoundMessage;
+
+const DOMAIN: &[u8] = b"sumcheck_mle/fiat-shamir/v1";
+
+/// A SHA-256 transcript the challenges of a proof are squeezed from.
+#[derive(Debug, Clone)]
+pub struct FsTranscript {
+    hasher: Sha256,
+}
+
+impl FsTranscript {
+    /// Starts the transcript of a proof that the sum of a polynomial in `num_vars`
+    /// variables over the hypercube is `claim`.
+    pub fn new<F: Field>(num_vars: usize, claim: F) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(DOMAIN);
+        hasher.update((num_vars as u64).to_le_bytes());
+        let mut transcript = Self { hasher };
+        transcript.absorb(&[claim]);
+        transcript
+    }
+
+    /// Appends `values`, prefixed by their number.
+    pub fn absorb<F: Field>(&mut self, values: &[F]) {
+        let mut bytes = Vec::new();
+        for value in values {
+            value.serialize_compressed(&mut bytes).expect("serializing into a vector cannot fail");
+        }
+        self.hasher.update((values.len() as u64).to_le_bytes());
+        self.hasher.update(bytes);
+    }
+
+    /// Derives the next challenge from everything absorbed so far, then absorbs it.
+    pub fn challenge<F: Field>(&mut self) -> F {
+        // hashes that do not decode to an element are retried with the next counter
+        for counter in 0u64.. {
+            let digest = self.hasher.clone().chain_update(b"challenge").chain_update(counter.to_le_bytes()).finalize();
+            if let Some(r) = F::from_random_bytes(&digest) {
+                self.absorb(&[r]);
+                return r;
+            }
+        }
+        unreachable!()
+    }
+}
+
+/// The round messages of a non-interactive run. The challenges are not part of the proof,
+/// the verifier derives them from the messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proof<M> {
+    pub messages: Vec<M>,
+}
+
+/// What a proof reduces the claim to: $g(point) = value$, left to the oracle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubClaim<F: Field> {
+    pub point: Vec<F>,
+    pub value: F,
+}
+
+/// Runs `prover` against challenges derived by an [`FsTranscript`], proving its own claim.
+/// A partial prover plays only its [`SumcheckProver::rounds`], so its proof has that many
+/// messages.
+pub fn prove<F: Field, P: SumcheckProver<F>>(mut prover: P) -> Result<Proof<P::Message>, Error<F>> {
+    let rounds = prover.rounds();
+    let mut transcript = FsTranscript::new(prover.num_vars(), prover.claim());
+    let mut messages = Vec::with_capacity(rounds);
+
+    // the first round ignores the challenge
+    let mut r_j = F::one();
+    for j in 0..rounds {
+        let message = prover.round(r_j, j)?;
+        transcript.absorb(&message.to_round_polynomial()?.to_coefficients());
+        r_j = transcript.challenge();
+        messages.push(message);
+    }
+    Ok(Proof { messages })
+}
+
+/// Checks every round of `proof` for `claim`, with the round polynomial of round $j$ of
+/// degree at most `degrees[j]`, and returns what is left to check against the oracle. The
+/// challenge of round $j$ is $x_j$ of the point, so the provers have to bind the variables
+/// in the default [`crate::poly::BindingOrder`].
+pub(crate) fn verify_rounds<F: Field, M: RoundMessage<F>>(
+    proof: &Proof<M>,
+    claim: F,
+    degrees: &[usize],
+) -> Result<SubClaim<F>, Error<F>> {
+    if proof.messages.len() != degrees.len() {
+        return Err(Error::WrongProofLength(degrees.len(), proof.messages.len()));
+    }
+    let mut transcript = FsTranscript::new(degrees.len(), claim);
+    let mut point = Vec::with_capacity(degrees.len());
+    let mut expected = claim;
+    for (j, (message, bound)) in proof.messages.iter().zip(degrees).enumerate() {
+        let g_j = message.to_round_polynomial()?;
+        if g_j.degree() > *bound {
+            return Err(Error::DegreeTooHigh(j, g_j.degree(), *bound));
+        }
+        let sum = g_j.sum_over_boolean();
+        if sum != expected {
+            return Err(Error::ClaimMismatch(j, expected, sum));
+        }
+        transcript.absorb(&g_j.to_coefficients());
+        let r_j = transcript.challenge();
+        expected = g_j.evaluate(r_j);
+        point.push(r_j);
+    }
+    Ok(SubClaim { point, value: expected })
+}
+
+/// Accepts `subclaim` if the oracle agrees with it.
+pub(crate) fn check_oracle<F: Field>(subclaim: SubClaim<F>, oracle: F) -> Result<SubClaim<F>, Error<F>> {
+    if oracle != subclaim.value {
+        return Err(Error::OracleMismatch(subclaim.value, oracle));
+    }
+    Ok(subclaim)
+}
+
+/// Checks the rounds of every proof, then asks `oracles` once for the values at the points
+/// of all proofs that got that far. The results are in the order of `proofs`, an oracle
+/// that cannot answer, or answers a different number of points, fails the whole batch.
+pub(crate) fn verify_batch_with<F, M>(
+    proofs: &[(Proof<M>, F)],
+    degrees: &[usize],
+    oracles: impl FnOnce(&[Vec<F>]) -> Result<Vec<F>, Error<F>>,
+) -> Result<BatchReport<F>, Error<F>>
+where
+    F: Field,
+    M: RoundMessage<F>,
+{
+    let results: Vec<_> = proofs.iter().map(|(proof, claim)| verify_rounds(proof, *claim, degrees)).collect();
+    let points: Vec<Vec<F>> = results.iter().flatten().map(|subclaim| subclaim.point.clone()).collect();
+    let values = oracles(&points)?;
+    if values.len() != points.len() {
+        return Err(Error::WrongOracleAnswerCount(points.len(), values.len()));
+    }
+    let mut values = values.into_iter();
+    let results = results.into_iter().map(|result| check_oracle(result?, values.next().unwrap())).collect();
+    Ok(BatchReport { results })
+}
+
+/// The results of a batch verification, one per proof in the order they were given.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchReport<F: Field> {
+    pub results: Vec<Result<SubClaim<F>, Error<F>>>,
+}
+
+impl<F: Field> BatchReport<F> {
+    /// Returns whether every proof was accepted.
+    pub fn all_accepted(&self) -> bool {
+        self.results.iter().all(Result::is_ok)
+    }
+
+    /// Returns the index of every rejected proof with the reason.
+    pub fn failures(&self) -> impl Iterator<Item = (usize, &Error<F>)> {
+        self.results.iter().enumerate().filter_map(|(i, result)| result.as_ref().err().map(|e| (i, e)))
+    }
+}
@@ -0,0 +1,147 @@
+use ark_ff::{Field, One};
+use ark_poly::univariate;
+
+use crate::error::Error;
+use crate::fiat_shamir::*;
+use crate::fields::Fp97;
+use crate::oracle::Product;
+use crate::parser::{parse_factors, parse_polynomial};
+use crate::poly::{Mle, OracleEvaluation};
+use crate::soundness::SecurityConfig;
+use crate::{sumcheck_ml, sumcheck_naive, sumcheck_pml};
+
+fn insecure() -> SecurityConfig {
+    SecurityConfig::default().allow_insecure()
+}
+
+fn prove_and_verify<F: Field>() {
+    let g = parse_polynomial::<F>("24*x0 + 15*x0*x1 + 35*x1*x2 + 3*x2").unwrap();
+    let prover = sumcheck_ml::prover::Prover::new(g.clone()).unwrap();
+    let claim = prover.claim();
+    let proof = prove(prover).unwrap();
+    let subclaim = sumcheck_ml::verify(&proof, claim, &mut g.clone(), insecure()).unwrap();
+    assert_eq!(g.evaluate(&subclaim.point), Some(subclaim.value));
+
+    let factors = parse_factors::<F>("(x0 + 2*x1) * (3*x0*x1 + x2) * (x0^2 + x2)").unwrap();
+    let prover = sumcheck_pml::prover::Prover::new(factors.clone()).unwrap();
+    let claim = prover.claim();
+    let proof = prove(prover).unwrap();
+    assert!(sumcheck_pml::verify(&proof, claim, &mut Product::new(factors).unwrap(), insecure()).is_ok());
+
+    let g = parse_polynomial::<F>("2*x0^3 + x0*x2 + x1*x2").unwrap();
+    let prover = sumcheck_naive::Prover::new(g.clone()).unwrap();
+    let claim = prover.c_1();
+    let proof = prove(prover).unwrap();
+    assert!(sumcheck_naive::verify(&proof, claim, &mut g.clone(), insecure()).is_ok());
+}
+
+#[test]
+fn test_prove_and_verify(){
+    for_each_field!(F => prove_and_verify::<F>());
+}
+
+#[test]
+fn test_transcript(){
+    let mut a = FsTranscript::new(3, Fp97::from(5u64));
+    let mut b = a.clone();
+    assert_eq!(a.challenge::<Fp97>(), b.challenge::<Fp97>());
+
+    // the challenges depend on everything absorbed before
+    a.absorb(&[Fp97::one()]);
+    b.absorb(&[Fp97::one(), Fp97::one()]);
+    assert_ne!(a.challenge::<Fp97>(), b.challenge::<Fp97>());
+    let mut c = FsTranscript::new(3, Fp97::from(6u64));
+    assert_ne!(FsTranscript::new(3, Fp97::from(5u64)).challenge::<Fp97>(), c.challenge::<Fp97>());
+}
+
+#[test]
+fn test_security(){
+    // 97 elements leave a cheating prover far below the default level, and offline it can
+    // retry challenges until one passes
+    let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1 + 35*x1*x2 + 3*x2").unwrap();
+    let prover = sumcheck_ml::prover::Prover::new(g.clone()).unwrap();
+    let claim = prover.claim();
+    let proof = prove(prover).unwrap();
+    let secure = SecurityConfig::default();
+    assert!(matches!(sumcheck_ml::verify(&proof, claim, &mut g.clone(), secure), Err(Error::InsufficientSecurity(..))));
+    assert!(matches!(sumcheck_ml::verify_batch(&[(proof.clone(), claim)], &mut g.clone(), secure), Err(Error::InsufficientSecurity(..))));
+    assert!(sumcheck_ml::verify(&proof, claim, &mut g.clone(), insecure()).is_ok());
+
+    let factors = parse_factors::<Fp97>("(x0 + 2*x1) * (3*x0*x1 + x2)").unwrap();
+    let prover = sumcheck_pml::prover::Prover::new(factors.clone()).unwrap();
+    let claim = prover.claim();
+    let proof = prove(prover).unwrap();
+    let mut oracle = Product::new(factors).unwrap();
+    assert!(matches!(sumcheck_pml::verify(&proof, claim, &mut oracle, secure), Err(Error::InsufficientSecurity(..))));
+    assert!(sumcheck_pml::verify(&proof, claim, &mut oracle, insecure()).is_ok());
+
+    let g = parse_polynomial::<Fp97>("2*x0^3 + x0*x2 + x1*x2").unwrap();
+    let prover = sumcheck_naive::Prover::new(g.clone()).unwrap();
+    let claim = prover.c_1();
+    let proof = prove(prover).unwrap();
+    assert!(matches!(sumcheck_naive::verify(&proof, claim, &mut g.clone(), secure), Err(Error::InsufficientSecurity(..))));
+    assert!(sumcheck_naive::verify(&proof, claim, &mut g.clone(), insecure()).is_ok());
+}
+
+#[test]
+fn test_rejects_tampered_proofs(){
+    let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1 + 35*x1*x2 + 3*x2").unwrap();
+    let prover = sumcheck_ml::prover::Prover::new(g.clone()).unwrap();
+    let claim = prover.claim();
+    let proof = prove(prover).unwrap();
+
+    let wrong_claim = claim + Fp97::one();
+    assert!(matches!(sumcheck_ml::verify(&proof, wrong_claim, &mut g.clone(), insecure()), Err(Error::ClaimMismatch(0, ..))));
+
+    let short = Proof { messages: proof.messages[..2].to_vec() };
+    assert_eq!(sumcheck_ml::verify(&short, claim, &mut g.clone(), insecure()), Err(Error::WrongProofLength(3, 2)));
+
+    // a consistent change of the last round moves the final value off $g$
+    let mut tampered = proof.clone();
+    let (p0, p1) = tampered.messages[2];
+    tampered.messages[2] = (p0 + Fp97::one(), p1 - Fp97::one());
+    assert!(matches!(sumcheck_ml::verify(&tampered, claim, &mut g.clone(), insecure()), Err(Error::OracleMismatch(..))));
+
+    // the naive verifier bounds the degree of every round
+    let g = parse_polynomial::<Fp97>("2*x0^3 + x0*x2 + x1*x2").unwrap();
+    let prover = sumcheck_naive::Prover::new(g.clone()).unwrap();
+    let claim = prover.c_1();
+    let mut proof = prove(prover).unwrap();
+    proof.messages[1] = univariate::SparsePolynomial::from_coefficients_vec(vec![(0, Fp97::from(20u64)), (2, Fp97::from(10u64))]);
+    assert_eq!(sumcheck_naive::verify(&proof, claim, &mut g.clone(), insecure()), Err(Error::DegreeTooHigh(1, 2, 1)));
+}
+
+#[test]
+fn test_batch(){
+    let g = Mle::from_fn(4, |b| Fp97::from((b * b + 3) as u64));
+    let claim = sumcheck_ml::prover::Prover::new(g.clone()).unwrap().claim();
+    let proof = prove(sumcheck_ml::prover::Prover::new(g.clone()).unwrap()).unwrap();
+    let mut tampered = proof.clone();
+    let (p0, p1) = tampered.messages[3];
+    tampered.messages[3] = (p0 - Fp97::one(), p1 + Fp97::one());
+    let proofs = vec![
+        (proof.clone(), claim),
+        (proof.clone(), claim + Fp97::one()),
+        (tampered, claim),
+        (proof, claim),
+    ];
+
+    let report = sumcheck_ml::verify_batch(&proofs, &mut g.clone(), insecure()).unwrap();
+    assert!(!report.all_accepted());
+    let failed: Vec<usize> = report.failures().map(|(i, _)| i).collect();
+    assert_eq!(failed, vec![1, 2]);
+    for ((proof, claim), result) in proofs.iter().zip(&report.results) {
+        assert_eq!(&sumcheck_ml::verify(proof, *claim, &mut g.clone(), insecure()), result);
+    }
+
+    let factors = vec![g.clone(), Mle::from_fn(4, |b| Fp97::from(b as u64 + 1))];
+    let prover = sumcheck_pml::prover::Prover::new(factors.clone()).unwrap();
+    let claim = prover.claim();
+    let proof = prove(prover).unwrap();
+    let report = sumcheck_pml::verify_batch(&[(proof.clone(), claim), (proof, claim)], &mut Product::new(factors).unwrap(), insecure()).unwrap();
+    assert!(report.all_accepted());
+
+    // an oracle dropping an answer fails the batch instead of leaving a proof unchecked
+    let short = verify_batch_with(&proofs, &[1; 4], |points| Ok(points[1..].iter().map(|point| g.evaluate(point).unwrap()).collect()));
+    assert_eq!(short, Err(Error::WrongOracleAnswerCount(3, 2)));
+}
@@ -0,0 +1,143 @@
+#![allow(non_local_definitions)]
+//! Ready-made fields to run the protocols over.
+//!
+//! Apart from [`Fp97`] every field sits behind a cargo feature of the same name:
+//! `goldilocks` and `mersenne31` are enabled by default, `bn254` and `bls12-381` pull in
+//! the scalar fields of the respective curves.
+use ark_ff::fields::{Fp64, MontBackend, MontConfig};
+#[cfg(feature = "mersenne31")]
+use ark_ff::{fields::{Fp2, Fp2Config}, MontFp};
+
+#[derive(MontConfig)]
+#[modulus = "97"]
+#[generator = "5"]
+pub struct Fp97Config;
+
+/// A 97-element field. Far too small to be sound, but handy for tests and examples where
+/// every value is readable.
+pub type Fp97 = Fp64<MontBackend<Fp97Config, 1>>;
+
+// https://cronokirby.com/notes/2022/09/the-goldilocks-field/
+#[cfg(feature = "goldilocks")]
+#[derive(MontConfig)]
+#[modulus = "18446744069414584321"]
+#[generator = "7"]
+pub struct GoldilocksConfig;
+
+/// The Goldilocks field of $p = 2^{64} - 2^{32} + 1$ elements.
+#[cfg(feature = "goldilocks")]
+pub type Goldilocks = Fp64<MontBackend<GoldilocksConfig, 1>>;
+
+#[cfg(feature = "mersenne31")]
+#[derive(MontConfig)]
+#[modulus = "2147483647"]
+#[generator = "7"]
+pub struct M31Config;
+
+/// The Mersenne-31 field of $p = 2^{31} - 1$ elements.
+#[cfg(feature = "mersenne31")]
+pub type M31 = Fp64<MontBackend<M31Config, 1>>;
+
+#[cfg(feature = "mersenne31")]
+pub struct CM31Config;
+
+/// $p \equiv 3 \pmod 4$, so $-1$ is a non-residue and $\mathbb{F}_p[i]/(i^2 + 1)$ is a field.
+#[cfg(feature = "mersenne31")]
+impl Fp2Config for CM31Config {
+    type Fp = M31;
+
+    const NONRESIDUE: M31 = MontFp!("2147483646");
+
+    const FROBENIUS_COEFF_FP2_C1: &'static [M31] = &[MontFp!("1"), MontFp!("2147483646")];
+
+    #[inline(always)]
+    fn mul_fp_by_nonresidue_in_place(fe: &mut M31) -> &mut M31 {
+        *fe = -*fe;
+        fe
+    }
+}
+
+/// The complex extension of [`M31`] with $p^2 \approx 2^{62}$ elements, which takes the
+/// challenges of the protocols out of the base field.
+#[cfg(feature = "mersenne31")]
+pub type CM31 = Fp2<CM31Config>;
+
+/// The scalar field of the BN254 curve.
+#[cfg(feature = "bn254")]
+pub use ark_bn254::Fr as Bn254Fr;
+
+/// The scalar field of the BLS12-381 curve.
+#[cfg(feature = "bls12-381")]
+pub use ark_bls12_381::Fr as Bls12_381Fr;
+
+/// Runs `$body` once for [`Fp97`] and once for every enabled field preset, with `$F` bound
+/// to the field.
+#[cfg(test)]
+macro_rules! for_each_field {
+    ($F:ident => $body:expr) => {{
+        {
+            type $F = $crate::fields::Fp97;
+            $body;
+        }
+        #[cfg(feature = "goldilocks")]
+        {
+            type $F = $crate::fields::Goldilocks;
+            $body;
+        }
+        #[cfg(feature = "mersenne31")]
+        {
+            type $F = $crate::fields::M31;
+            $body;
+        }
+        #[cfg(feature = "mersenne31")]
+        {
+            type $F = $crate::fields::CM31;
+            $body;
+        }
+        #[cfg(feature = "bn254")]
+        {
+            type $F = $crate::fields::Bn254Fr;
+            $body;
+        }
+        #[cfg(feature = "bls12-381")]
+        {
+            type $F = $crate::fields::Bls12_381Fr;
+            $body;
+        }
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ff::Field;
+    use ark_std::test_rng;
+
+    fn field_axioms<F: Field>() {
+        let rng = &mut test_rng();
+        for _ in 0..32 {
+            let (a, b) = (F::rand(rng), F::rand(rng));
+            assert_eq!(a * b, b * a);
+            assert_eq!((a + b) * (a - b), a.square() - b.square());
+            if !a.is_zero() {
+                assert_eq!(a * a.inverse().unwrap(), F::one());
+            }
+        }
+    }
+
+    #[test]
+    fn test_field_presets(){
+        for_each_field!(F => field_axioms::<F>());
+    }
+
+    #[cfg(feature = "mersenne31")]
+    #[test]
+    fn test_cm31(){
+        use super::{CM31, M31};
+        use crate::soundness::log2_field_size;
+
+        let i = CM31::new(M31::from(0u8), M31::from(1u8));
+        assert_eq!(i.square(), -CM31::from(1u8));
+        assert!((log2_field_size::<CM31>() - 62.0).abs() < 1e-6);
+        assert!((log2_field_size::<M31>() - 31.0).abs() < 1e-6);
+    }
+}
@@ -1,9 +1,23 @@
+#[macro_use]
+pub mod fields;
+pub mod error;
+pub mod poly;
+pub mod oracle;
 pub mod sumcheck_ml;
 pub mod sumcheck_pml;
 pub mod sumcheck_naive;
+pub mod parser;
+pub mod transport;
+pub mod protocol;
+pub mod round_poly;
+pub mod session;
+pub mod adversary;
+pub mod soundness;
+pub mod repetition;
+pub mod fiat_shamir;
 
 #[cfg(test)]
-mod tests {}
+mod tests;
 
 pub fn sort_arr<T:Ord + std::marker::Copy>(arr:&mut [T]){
     sorting::merge_sort(arr);
@@ -61,7 +75,7 @@ pub mod sorting{
       
       pub fn quick_sort_partition<T:Ord + std::marker::Copy>(array: &mut [T], start: isize, end: isize) {
         if start < end && end - start >= 1 {
-          let pivot = partition(array, start as isize, end as isize);
+          let pivot = partition(array, start, end);
           quick_sort_partition(array, start, pivot - 1);
           quick_sort_partition(array, pivot + 1, end);
         }
@@ -73,7 +87,7 @@ pub mod sorting{
       
         for j in l..h {
           if array[j as usize] <= pivot {
-            i = i + 1;
+            i += 1;
             array.swap(i as usize, j as usize);
           }
         }
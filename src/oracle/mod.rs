@@ -0,0 +1,246 @@
+//! Oracle access to $g$ for the verifiers.
+//!
+//! A verifier only looks at $g$ in its last round, to compare the prover's final value with
+//! $g$ at the point the challenges make up. [`EvaluationOracle`] is that access, so the same
+//! verifier works whether it holds $g$ or only a commitment to it:
+//!
+//! - a sparse polynomial held locally, or an [`Mle`] for a precomputed table of values
+//!   over the hypercube,
+//! - [`Product`] of factors held locally, the oracle of [`crate::sumcheck_pml`],
+//! - [`CommitmentOracle`], asking the prover for an opening of a commitment to $g$,
+//! - [`CountingOracle`], counting the queries to another oracle.
+#[cfg(test)]
+mod test;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use ark_ff::Field;
+use ark_poly::multivariate::{self, SparseTerm};
+
+use crate::error::Error;
+use crate::poly::{Mle, OracleEvaluation};
+
+/// Query access to a polynomial $g$ in $n$ variables.
+pub trait EvaluationOracle<F: Field> {
+    /// Returns the number of variables of $g$.
+    fn num_vars(&self) -> usize;
+
+    /// Returns a bound on the degree of $g$ in each of its variables.
+    fn degrees(&self) -> Vec<usize>;
+
+    /// Returns $g(point)$, or the reason the oracle cannot answer.
+    fn query(&mut self, point: &[F]) -> Result<F, Error<F>>;
+
+    /// Returns $g$ at every point. Implementations share work across the points where they can.
+    fn query_many(&mut self, points: &[Vec<F>]) -> Result<Vec<F>, Error<F>> {
+        points.iter().map(|point| self.query(point)).collect()
+    }
+}
+
+/// A sparse polynomial held by the verifier.
+impl<F: Field> EvaluationOracle<F> for multivariate::SparsePolynomial<F, SparseTerm> {
+    fn num_vars(&self) -> usize {
+        OracleEvaluation::num_vars(self)
+    }
+
+    fn degrees(&self) -> Vec<usize> {
+        OracleEvaluation::degrees(self)
+    }
+
+    fn query(&mut self, point: &[F]) -> Result<F, Error<F>> {
+        evaluate(self, point)
+    }
+}
+
+/// A table held by the verifier, queried for its multilinear extension.
+impl<F: Field> EvaluationOracle<F> for Mle<F> {
+    fn num_vars(&self) -> usize {
+        OracleEvaluation::num_vars(self)
+    }
+
+    fn degrees(&self) -> Vec<usize> {
+        OracleEvaluation::degrees(self)
+    }
+
+    fn query(&mut self, point: &[F]) -> Result<F, Error<F>> {
+        evaluate(self, point)
+    }
+
+    /// Folds the table once per point, see [`OracleEvaluation::evaluate_at_points`].
+    fn query_many(&mut self, points: &[Vec<F>]) -> Result<Vec<F>, Error<F>> {
+        check_points(OracleEvaluation::num_vars(self), points)?;
+        Ok(self.evaluate_at_points(points).unwrap())
+    }
+}
+
+fn evaluate<F: Field, P: OracleEvaluation<F>>(g: &P, point: &[F]) -> Result<F, Error<F>> {
+    // a sparse polynomial panics on a short point and ignores the rest of a long one
+    check_points(g.num_vars(), std::slice::from_ref(&point))?;
+    Ok(g.evaluate(point).unwrap())
+}
+
+fn check_points<F: Field, T: AsRef<[F]>>(num_vars: usize, points: &[T]) -> Result<(), Error<F>> {
+    match points.iter().map(AsRef::as_ref).find(|point| point.len() != num_vars) {
+        Some(point) => Err(Error::NumVarsMismatch(num_vars, point.len())),
+        None => Ok(()),
+    }
+}
+
+/// The product of factors held by the verifier, all in the same variables.
+#[derive(Debug, Clone)]
+pub struct Product<P> {
+    factors: Vec<P>,
+}
+
+impl<P> Product<P> {
+    /// Fails unless there is a factor and all of them have the same, non-zero number of
+    /// variables.
+    pub fn new<F: Field>(factors: Vec<P>) -> Result<Self, Error<F>>
+    where
+        P: OracleEvaluation<F>,
+    {
+        let num_vars = factors.first().ok_or(Error::EmptyInput)?.num_vars();
+        if num_vars == 0 {
+            return Err(Error::EmptyInput);
+        }
+        if let Some(f) = factors.iter().find(|f| f.num_vars() != num_vars) {
+            return Err(Error::NumVarsMismatch(num_vars, f.num_vars()));
+        }
+        Ok(Self { factors })
+    }
+
+    pub fn factors(&self) -> &[P] {
+        &self.factors
+    }
+}
+
+impl<F: Field, P: OracleEvaluation<F>> EvaluationOracle<F> for Product<P> {
+    fn num_vars(&self) -> usize {
+        self.factors[0].num_vars()
+    }
+
+    /// The degrees of the factors add up in every variable.
+    fn degrees(&self) -> Vec<usize> {
+        let mut degrees = vec![0; self.factors[0].num_vars()];
+        for f in &self.factors {
+            for (d, d_f) in degrees.iter_mut().zip(f.degrees()) {
+                *d += d_f;
+            }
+        }
+        degrees
+    }
+
+    /// Evaluates the factors together, see [`OracleEvaluation::evaluate_many`].
+    fn query(&mut self, point: &[F]) -> Result<F, Error<F>> {
+        check_points(self.factors[0].num_vars(), std::slice::from_ref(&point))?;
+        Ok(P::evaluate_many(&self.factors, point).unwrap().into_iter().product())
+    }
+
+    /// Evaluates every factor at all points in one go, see
+    /// [`OracleEvaluation::evaluate_at_points`].
+    fn query_many(&mut self, points: &[Vec<F>]) -> Result<Vec<F>, Error<F>> {
+        check_points(self.factors[0].num_vars(), points)?;
+        let mut values = vec![F::one(); points.len()];
+        for f in &self.factors {
+            for (value, f_value) in values.iter_mut().zip(f.evaluate_at_points(points).unwrap()) {
+                *value *= f_value;
+            }
+        }
+        Ok(values)
+    }
+}
+
+/// A scheme committing to polynomials and proving their evaluations.
+pub trait CommitmentScheme<F: Field> {
+    type Commitment;
+    type Proof;
+
+    /// Checks that `value` is the evaluation at `point` of the polynomial behind
+    /// `commitment`.
+    fn check(&self, commitment: &Self::Commitment, point: &[F], value: F, proof: &Self::Proof) -> bool;
+}
+
+/// Access to $g$ through a commitment. Every query asks `open`, usually the prover, for the
+/// value at the point with a proof, and accepts the value only if the proof checks out.
+pub struct CommitmentOracle<F: Field, C: CommitmentScheme<F>, O> {
+    scheme: C,
+    commitment: C::Commitment,
+    degrees: Vec<usize>,
+    open: O,
+}
+
+impl<F, C, O> CommitmentOracle<F, C, O>
+where
+    F: Field,
+    C: CommitmentScheme<F>,
+    O: FnMut(&[F]) -> (F, C::Proof),
+{
+    /// Access to the polynomial behind `commitment`, of at most the given degree in each
+    /// of its variables.
+    pub fn new(scheme: C, commitment: C::Commitment, degrees: Vec<usize>, open: O) -> Self {
+        Self { scheme, commitment, degrees, open }
+    }
+}
+
+impl<F, C, O> EvaluationOracle<F> for CommitmentOracle<F, C, O>
+where
+    F: Field,
+    C: CommitmentScheme<F>,
+    O: FnMut(&[F]) -> (F, C::Proof),
+{
+    fn num_vars(&self) -> usize {
+        self.degrees.len()
+    }
+
+    fn degrees(&self) -> Vec<usize> {
+        self.degrees.clone()
+    }
+
+    fn query(&mut self, point: &[F]) -> Result<F, Error<F>> {
+        let (value, proof) = (self.open)(point);
+        if !self.scheme.check(&self.commitment, point, value, &proof) {
+            return Err(Error::InvalidOpening);
+        }
+        Ok(value)
+    }
+}
+
+/// Counts the points another oracle is queried at. Clones share the count, so a clone kept
+/// aside still reads it after the verifier has consumed the oracle.
+#[derive(Debug, Clone)]
+pub struct CountingOracle<O> {
+    inner: O,
+    queries: Arc<AtomicUsize>,
+}
+
+impl<O> CountingOracle<O> {
+    pub fn new(inner: O) -> Self {
+        Self { inner, queries: Arc::new(AtomicUsize::new(0)) }
+    }
+
+    /// Returns the number of points queried so far.
+    pub fn queries(&self) -> usize {
+        self.queries.load(Ordering::Relaxed)
+    }
+}
+
+impl<F: Field, O: EvaluationOracle<F>> EvaluationOracle<F> for CountingOracle<O> {
+    fn num_vars(&self) -> usize {
+        self.inner.num_vars()
+    }
+
+    fn degrees(&self) -> Vec<usize> {
+        self.inner.degrees()
+    }
+
+    fn query(&mut self, point: &[F]) -> Result<F, Error<F>> {
+        self.queries.fetch_add(1, Ordering::Relaxed);
+        self.inner.query(point)
+    }
+
+    fn query_many(&mut self, points: &[Vec<F>]) -> Result<Vec<F>, Error<F>> {
+        self.queries.fetch_add(points.len(), Ordering::Relaxed);
+        self.inner.query_many(points)
+    }
+}
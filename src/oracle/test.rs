@@ -0,0 +1,110 @@
+use ark_ff::{Field, One};
+use ark_serialize::CanonicalSerialize;
+use ark_std::test_rng;
+use sha2::{Digest, Sha256};
+
+use crate::error::Error;
+use crate::fields::Fp97;
+use crate::oracle::*;
+use crate::parser::{parse_factors, parse_polynomial};
+use crate::poly::{evaluate_table, HypercubeEvaluation, Mle};
+use crate::protocol::run_protocol;
+use crate::{sumcheck_ml, sumcheck_naive, sumcheck_pml};
+
+/// Commits to the table of a multilinear polynomial by its hash, an opening is the whole
+/// table. Binding but neither hiding nor succinct, enough to exercise [`CommitmentOracle`].
+struct TableHash;
+
+impl TableHash {
+    fn commit<F: Field>(table: &[F]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        table.serialize_compressed(&mut bytes).unwrap();
+        Sha256::digest(bytes).to_vec()
+    }
+}
+
+impl<F: Field> CommitmentScheme<F> for TableHash {
+    type Commitment = Vec<u8>;
+    type Proof = Vec<F>;
+
+    fn check(&self, commitment: &Vec<u8>, point: &[F], value: F, proof: &Vec<F>) -> bool {
+        *commitment == Self::commit(proof) && evaluate_table(proof, point) == value
+    }
+}
+
+fn counting<F: Field>() {
+    let rng = &mut test_rng();
+
+    let g = parse_polynomial::<F>("24*x0 + 15*x0*x1 + 35*x1*x2 + 3*x2").unwrap();
+    let oracle = CountingOracle::new(g.clone());
+    let prover = sumcheck_ml::prover::Prover::new(g).unwrap();
+    let verifier = |claim| sumcheck_ml::verifier::Verifier::with_options(oracle.clone(), claim, sumcheck_ml::verifier::VerifierOptions::default().allow_insecure()).unwrap();
+    assert!(run_protocol(prover, verifier, rng).unwrap().accepted);
+    assert_eq!(oracle.queries(), 1);
+
+    let factors = parse_factors::<F>("(x0 + 2*x1) * (3*x0*x1 + x2) * (x0^2 + x2)").unwrap();
+    let oracle = CountingOracle::new(Product::new(factors.clone()).unwrap());
+    let prover = sumcheck_pml::prover::Prover::new(factors).unwrap();
+    let verifier = |claim| sumcheck_pml::verifier::Verifier::with_options(oracle.clone(), claim, sumcheck_pml::verifier::VerifierOptions::default().allow_insecure()).unwrap();
+    assert!(run_protocol(prover, verifier, rng).unwrap().accepted);
+    assert_eq!(oracle.queries(), 1);
+
+    let g = parse_polynomial::<F>("2*x0^3 + x0*x2 + x1*x2").unwrap();
+    let oracle = CountingOracle::new(g.clone());
+    let prover = sumcheck_naive::Prover::new(g).unwrap();
+    let verifier = |c_1| {
+        let mut verifier = sumcheck_naive::Verifier::with_options(oracle.clone(), sumcheck_naive::VerifierOptions::default().allow_insecure()).unwrap();
+        verifier.set_c_1(c_1);
+        verifier
+    };
+    assert!(run_protocol(prover, verifier, rng).unwrap().accepted);
+    assert_eq!(oracle.queries(), 1);
+}
+
+#[test]
+fn test_counting(){
+    for_each_field!(F => counting::<F>());
+}
+
+#[test]
+fn test_commitment(){
+    let rng = &mut test_rng();
+    let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1 + 35*x1*x2 + 3*x2").unwrap();
+    let table = g.to_evaluations();
+    let commitment = TableHash::commit(&table);
+
+    // the prover opens honestly
+    let open = |point: &[Fp97]| (evaluate_table(&table, point), table.clone());
+    let oracle = CommitmentOracle::new(TableHash, commitment.clone(), vec![1; 3], open);
+    let prover = sumcheck_ml::prover::Prover::new(g.clone()).unwrap();
+    let verifier = |claim| sumcheck_ml::verifier::Verifier::with_options(oracle, claim, sumcheck_ml::verifier::VerifierOptions::default().allow_insecure()).unwrap();
+    assert!(run_protocol(prover, verifier, rng).unwrap().accepted);
+
+    // an opening of another table does not match the commitment
+    let mut other = table.clone();
+    other[5] += Fp97::one();
+    let open = |point: &[Fp97]| (evaluate_table(&other, point), other.clone());
+    let oracle = CommitmentOracle::new(TableHash, commitment, vec![1; 3], open);
+    let prover = sumcheck_ml::prover::Prover::new(g).unwrap();
+    let verifier = |claim| sumcheck_ml::verifier::Verifier::with_options(oracle, claim, sumcheck_ml::verifier::VerifierOptions::default().allow_insecure()).unwrap();
+    assert_eq!(run_protocol(prover, verifier, rng).unwrap_err(), Error::InvalidOpening);
+}
+
+#[test]
+fn test_product(){
+    let factors = parse_factors::<Fp97>("(x0 + 2*x1) * (x0*x1 + 3)").unwrap();
+    let mut product = Product::new(factors.clone()).unwrap();
+    assert_eq!(product.num_vars(), 2);
+    assert_eq!(product.degrees(), vec![2, 2]);
+
+    let points = vec![vec![Fp97::from(2u64), Fp97::from(5u64)], vec![Fp97::one(), Fp97::from(7u64)]];
+    // (2 + 10) * (10 + 3) and (1 + 14) * (7 + 3)
+    let expected = vec![Fp97::from(156u64), Fp97::from(150u64)];
+    assert_eq!(product.query_many(&points).unwrap(), expected);
+    assert_eq!(product.query(&points[0]).unwrap(), expected[0]);
+    assert_eq!(product.query(&[Fp97::one()]), Err(Error::NumVarsMismatch(2, 1)));
+
+    assert_eq!(Product::<Mle<Fp97>>::new(vec![]).unwrap_err(), Error::EmptyInput);
+    let mixed = vec![Mle::from_fn(2, |b| Fp97::from(b as u64)), Mle::from_fn(3, |b| Fp97::from(b as u64))];
+    assert_eq!(Product::new(mixed).unwrap_err(), Error::NumVarsMismatch(2, 3));
+}
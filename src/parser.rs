@@ -0,0 +1,339 @@
+use ark_ff::{Field, PrimeField};
+use ark_poly::{
+    multivariate::{SparsePolynomial, SparseTerm, Term},
+    DenseMVPolynomial,
+};
+
+/// An error produced while parsing a polynomial, positioned at a byte offset of the input.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseError {
+    #[error("unexpected character {1:?} at position {0}")]
+    UnexpectedChar(usize, char),
+
+    #[error("unexpected end of input at position {0}")]
+    UnexpectedEnd(usize),
+
+    #[error("expected a number at position {0}")]
+    ExpectedNumber(usize),
+
+    #[error("number at position {0} does not fit in usize")]
+    NumberOverflow(usize),
+
+    #[error("variable x{1} at position {0} is out of range for {2} variables")]
+    VariableOutOfRange(usize, usize, usize),
+}
+
+/// Parses a polynomial such as `24*x0 + 15*x0*x1 + 35*x1^2` over the field `F`.
+///
+/// Coefficients are reduced modulo the modulus of `F`, so `-1` and `96` parse to the
+/// same value over a 97-element field. Variables are written `x0`, `x1`, ... (or `x_0`).
+/// The number of variables is one more than the highest variable index that appears.
+pub fn parse_polynomial<F: Field>(input: &str) -> Result<SparsePolynomial<F, SparseTerm>, ParseError> {
+    let terms = Parser::new(input).polynomial_eof()?;
+    let num_vars = max_num_vars(&terms);
+    Ok(SparsePolynomial::from_coefficients_vec(num_vars, terms))
+}
+
+/// Same as [`parse_polynomial`] but with an explicit number of variables, which is needed
+/// when the highest variables do not appear in the input.
+pub fn parse_polynomial_with_num_vars<F: Field>(
+    input: &str,
+    num_vars: usize,
+) -> Result<SparsePolynomial<F, SparseTerm>, ParseError> {
+    let mut parser = Parser::new(input);
+    parser.num_vars = Some(num_vars);
+    let terms = parser.polynomial_eof()?;
+    Ok(SparsePolynomial::from_coefficients_vec(num_vars, terms))
+}
+
+/// Parses a product of parenthesised factors such as `(24*x0 + 35*x1) * (17*x0 + 33*x1)`
+/// into the list of factors used by [`crate::sumcheck_pml`].
+///
+/// All factors share the same number of variables. A single factor may omit the parentheses.
+pub fn parse_factors<F: Field>(input: &str) -> Result<Vec<SparsePolynomial<F, SparseTerm>>, ParseError> {
+    let factors = Parser::new(input).factors_eof()?;
+    let num_vars = factors.iter().map(|terms| max_num_vars(terms)).max().unwrap_or(0);
+    Ok(factors
+        .into_iter()
+        .map(|terms| SparsePolynomial::from_coefficients_vec(num_vars, terms))
+        .collect())
+}
+
+/// Same as [`parse_factors`] but with an explicit number of variables.
+pub fn parse_factors_with_num_vars<F: Field>(
+    input: &str,
+    num_vars: usize,
+) -> Result<Vec<SparsePolynomial<F, SparseTerm>>, ParseError> {
+    let mut parser = Parser::new(input);
+    parser.num_vars = Some(num_vars);
+    Ok(parser
+        .factors_eof()?
+        .into_iter()
+        .map(|terms| SparsePolynomial::from_coefficients_vec(num_vars, terms))
+        .collect())
+}
+
+/// Prints `poly` in the syntax accepted by [`parse_polynomial`].
+pub fn format_polynomial<F: PrimeField>(poly: &SparsePolynomial<F, SparseTerm>) -> String {
+    let terms: Vec<String> = poly
+        .terms()
+        .iter()
+        .filter(|(coeff, _)| !coeff.is_zero())
+        .map(|(coeff, term)| format_term(coeff, term))
+        .collect();
+
+    if terms.is_empty() {
+        "0".to_string()
+    } else {
+        terms.join(" + ")
+    }
+}
+
+/// Prints a list of factors in the syntax accepted by [`parse_factors`].
+pub fn format_factors<F: PrimeField>(factors: &[SparsePolynomial<F, SparseTerm>]) -> String {
+    factors
+        .iter()
+        .map(|f| format!("({})", format_polynomial(f)))
+        .collect::<Vec<_>>()
+        .join(" * ")
+}
+
+fn format_term<F: PrimeField>(coeff: &F, term: &SparseTerm) -> String {
+    let vars: Vec<String> = term
+        .iter()
+        .map(|(var, power)| match power {
+            1 => format!("x{}", var),
+            _ => format!("x{}^{}", var, power),
+        })
+        .collect();
+
+    if vars.is_empty() {
+        coeff.to_string()
+    } else if coeff.is_one() {
+        vars.join("*")
+    } else {
+        format!("{}*{}", coeff, vars.join("*"))
+    }
+}
+
+fn max_num_vars<F>(terms: &[(F, SparseTerm)]) -> usize {
+    terms
+        .iter()
+        .flat_map(|(_, term)| term.vars())
+        .map(|var| var + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    num_vars: Option<usize>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, pos: 0, num_vars: None }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.input[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.input[self.pos..].chars().next() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.input[self.pos..].chars().next() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.bump();
+                Ok(())
+            }
+            Some(c) => Err(ParseError::UnexpectedChar(self.pos, c)),
+            None => Err(ParseError::UnexpectedEnd(self.pos)),
+        }
+    }
+
+    fn eof(&mut self) -> Result<(), ParseError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(c) => Err(ParseError::UnexpectedChar(self.pos, c)),
+        }
+    }
+
+    fn polynomial_eof<F: Field>(&mut self) -> Result<Vec<(F, SparseTerm)>, ParseError> {
+        let terms = self.polynomial()?;
+        self.eof()?;
+        Ok(terms)
+    }
+
+    fn factors_eof<F: Field>(&mut self) -> Result<Vec<Vec<(F, SparseTerm)>>, ParseError> {
+        if self.peek() != Some('(') {
+            return Ok(vec![self.polynomial_eof()?]);
+        }
+
+        let mut factors = Vec::new();
+        loop {
+            self.expect('(')?;
+            factors.push(self.polynomial()?);
+            self.expect(')')?;
+            if self.peek() == Some('*') {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        self.eof()?;
+        Ok(factors)
+    }
+
+    // polynomial := ['-'] term (('+' | '-') term)*
+    fn polynomial<F: Field>(&mut self) -> Result<Vec<(F, SparseTerm)>, ParseError> {
+        let mut terms = Vec::new();
+        let mut negate = false;
+        if self.peek() == Some('-') {
+            self.bump();
+            negate = true;
+        }
+
+        loop {
+            let (coeff, term) = self.term::<F>()?;
+            terms.push((if negate { -coeff } else { coeff }, term));
+
+            match self.peek() {
+                Some('+') => negate = false,
+                Some('-') => negate = true,
+                _ => break,
+            }
+            self.bump();
+        }
+
+        Ok(terms)
+    }
+
+    // term := factor ('*' factor)*
+    fn term<F: Field>(&mut self) -> Result<(F, SparseTerm), ParseError> {
+        let mut coeff = F::one();
+        let mut vars = Vec::new();
+
+        loop {
+            match self.peek() {
+                Some('x') => vars.push(self.variable()?),
+                Some(c) if c.is_ascii_digit() => coeff *= self.number::<F>()?,
+                Some(c) => return Err(ParseError::UnexpectedChar(self.pos, c)),
+                None => return Err(ParseError::UnexpectedEnd(self.pos)),
+            }
+
+            if self.peek() == Some('*') {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+
+        Ok((coeff, SparseTerm::new(vars)))
+    }
+
+    // variable := 'x' ['_'] digits ['^' digits]
+    fn variable(&mut self) -> Result<(usize, usize), ParseError> {
+        let start = self.pos;
+        self.bump();
+        if self.input[self.pos..].starts_with('_') {
+            self.pos += 1;
+        }
+        let at = self.pos;
+        let var = self.digits().ok_or(ParseError::ExpectedNumber(at))?;
+        let var = var.parse::<usize>().map_err(|_| ParseError::NumberOverflow(at))?;
+        if let Some(num_vars) = self.num_vars {
+            if var >= num_vars {
+                return Err(ParseError::VariableOutOfRange(start, var, num_vars));
+            }
+        }
+
+        let mut power = 1;
+        if self.peek() == Some('^') {
+            self.bump();
+            self.skip_whitespace();
+            let at = self.pos;
+            let digits = self.digits().ok_or(ParseError::ExpectedNumber(at))?;
+            power = digits.parse::<usize>().map_err(|_| ParseError::NumberOverflow(at))?;
+        }
+
+        Ok((var, power))
+    }
+
+    fn number<F: Field>(&mut self) -> Result<F, ParseError> {
+        let at = self.pos;
+        let digits = self.digits().ok_or(ParseError::ExpectedNumber(at))?;
+        let ten = F::from(10u8);
+        Ok(digits
+            .bytes()
+            .fold(F::zero(), |acc, d| acc * ten + F::from(d - b'0')))
+    }
+
+    fn digits(&mut self) -> Option<&'a str> {
+        let start = self.pos;
+        let len = self.input[start..]
+            .bytes()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        self.pos += len;
+        (len > 0).then(|| &self.input[start..start + len])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ff::PrimeField;
+    use ark_poly::{multivariate::{SparsePolynomial, SparseTerm, Term}, DenseMVPolynomial};
+
+    use super::*;
+    use crate::fields::Fp97;
+
+    fn fp(n: u32) -> Fp97 {
+        Fp97::from_bigint(n.into()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_format(){
+        let g: SparsePolynomial<Fp97, SparseTerm> = parse_polynomial("24*x0 + 15*x0*x1 + 35*x1^2").unwrap();
+        let expected = SparsePolynomial::from_coefficients_slice(
+            2,
+            &[
+                (fp(24), SparseTerm::new(vec![(0, 1)])),
+                (fp(15), SparseTerm::new(vec![(0, 1), (1, 1)])),
+                (fp(35), SparseTerm::new(vec![(1, 2)])),
+            ],
+        );
+        assert_eq!(g, expected);
+        assert_eq!(parse_polynomial::<Fp97>(&format_polynomial(&g)).unwrap(), g);
+
+        // coefficients are reduced modulo 97 and subtraction is field negation
+        let h: SparsePolynomial<Fp97, SparseTerm> = parse_polynomial("100 - x_0 + 2*3*x_0").unwrap();
+        assert_eq!(format_polynomial(&h), "3 + 5*x0");
+
+        let g = parse_factors::<Fp97>("(24*x0 + 35*x1) * (17*x0*x1)").unwrap();
+        assert_eq!(g.len(), 2);
+        assert_eq!(DenseMVPolynomial::num_vars(&g[1]), 2);
+        assert_eq!(parse_factors::<Fp97>(&format_factors(&g)).unwrap(), g);
+
+        assert_eq!(parse_polynomial::<Fp97>("24*x0 +"), Err(ParseError::UnexpectedEnd(7)));
+        assert_eq!(parse_polynomial::<Fp97>("24*y0"), Err(ParseError::UnexpectedChar(3, 'y')));
+        assert_eq!(parse_polynomial::<Fp97>("x0^"), Err(ParseError::ExpectedNumber(3)));
+        assert_eq!(parse_polynomial_with_num_vars::<Fp97>("x0 + x2", 2), Err(ParseError::VariableOutOfRange(5, 2, 2)));
+        assert_eq!(parse_factors::<Fp97>("(x0) * (x1"), Err(ParseError::UnexpectedEnd(10)));
+    }
+}
@@ -0,0 +1,72 @@
+//! Evaluation of multilinear polynomials given by their table over the hypercube.
+//!
+//! The multilinear extension of a table $t$ is
+//! $\tilde t(r) = \sum_{b \in \{0,1\}^n} t[b] \cdot eq(b, r)$ with
+//! $eq(b, r) = \prod_i (b_i r_i + (1 - b_i)(1 - r_i))$, so once the $2^n$ values of
+//! $eq(\cdot, r)$ are known an evaluation is a single inner product. Evaluating many
+//! tables at one point shares that table; evaluating one table at many points folds it
+//! once per point in a single scratch buffer instead.
+use ark_ff::Field;
+
+/// Returns $eq(b, r)$ for every $b \in \{0,1\}^n$, indexed like the tables of
+/// [`super::HypercubeEvaluation`], in $O(2^n)$ multiplications.
+pub fn eq_table<F: Field>(r: &[F]) -> Vec<F> {
+    let mut table = Vec::with_capacity(1 << r.len());
+    table.push(F::one());
+    // variable $i$ doubles the table, the upper half having bit $i$ set
+    for r_i in r {
+        for k in 0..table.len() {
+            let high = table[k] * r_i;
+            table[k] -= high;
+            table.push(high);
+        }
+    }
+    table
+}
+
+/// Evaluates the multilinear extension of `table` at `r`.
+///
+/// Panics unless `table` has $2^{|r|}$ entries.
+pub fn evaluate_table<F: Field>(table: &[F], r: &[F]) -> F {
+    assert_eq!(table.len(), 1 << r.len(), "table does not match the number of variables");
+    table.iter().zip(eq_table(r)).map(|(t, eq)| eq * t).sum()
+}
+
+/// Evaluates the multilinear extension of every table at `r`, building the eq table once.
+///
+/// Panics unless every table has $2^{|r|}$ entries.
+pub fn evaluate_tables<F: Field, T: AsRef<[F]>>(tables: &[T], r: &[F]) -> Vec<F> {
+    let eq = eq_table(r);
+    tables
+        .iter()
+        .map(|table| {
+            let table = table.as_ref();
+            assert_eq!(table.len(), eq.len(), "table does not match the number of variables");
+            table.iter().zip(&eq).map(|(t, eq)| *eq * t).sum()
+        })
+        .collect()
+}
+
+/// Evaluates the multilinear extension of `table` at every point.
+///
+/// Panics unless `table` has $2^{|r|}$ entries for every point $r$.
+pub fn evaluate_table_at_points<F: Field>(table: &[F], points: &[Vec<F>]) -> Vec<F> {
+    let mut scratch = Vec::with_capacity(table.len());
+    points
+        .iter()
+        .map(|r| {
+            assert_eq!(table.len(), 1 << r.len(), "table does not match the number of variables");
+            scratch.clear();
+            scratch.extend_from_slice(table);
+            // bind $x_0$ first, halving the live part of the table each time
+            let mut len = table.len();
+            for r_i in r {
+                len /= 2;
+                for b in 0..len {
+                    scratch[b] = scratch[b << 1] + (scratch[(b << 1) + 1] - scratch[b << 1]) * r_i;
+                }
+            }
+            scratch[0]
+        })
+        .collect()
+}
@@ -0,0 +1,289 @@
+//! Iteration over the Boolean hypercube $\{0,1\}^n$ without allocating per point.
+//!
+//! A point is a bit mask with bit $i$ giving $x_i$, the same indexing as the evaluation
+//! tables of [`super::HypercubeEvaluation`]. A [`Hypercube`] is the full cube or a sub-cube
+//! with some variables fixed. Its points can be walked in index order ([`Points`]), in
+//! Gray-code order where consecutive points differ in one bit ([`GrayCode`]), accessed at
+//! random or split into independent chunks for parallel workers.
+use std::marker::PhantomData;
+
+use ark_ff::Field;
+use ark_poly::multivariate::{self, SparseTerm};
+
+/// The points of $\{0,1\}^n$ where the variables in `fixed_mask` are set to their bits in
+/// `fixed_bits`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hypercube {
+    num_vars: usize,
+    fixed_mask: usize,
+    fixed_bits: usize,
+}
+
+impl Hypercube {
+    /// The full hypercube in `num_vars` variables.
+    ///
+    /// Panics unless every point fits into a `usize`.
+    pub fn new(num_vars: usize) -> Self {
+        assert!(num_vars < usize::BITS as usize, "{num_vars} variables do not fit into a usize");
+        Self { num_vars, fixed_mask: 0, fixed_bits: 0 }
+    }
+
+    /// Returns the sub-cube with $x_{var}$ set to `bit`.
+    pub fn fix(self, var: usize, bit: bool) -> Self {
+        assert!(var < self.num_vars, "variable {var} out of range");
+        Self {
+            fixed_mask: self.fixed_mask | 1 << var,
+            fixed_bits: (self.fixed_bits & !(1 << var)) | (bit as usize) << var,
+            ..self
+        }
+    }
+
+    pub fn num_vars(&self) -> usize { self.num_vars }
+
+    /// Returns the number of variables that are not fixed.
+    pub fn free_vars(&self) -> usize {
+        self.num_vars - self.fixed_mask.count_ones() as usize
+    }
+
+    /// Returns the number of points, $2^{free}$.
+    pub fn len(&self) -> usize {
+        1 << self.free_vars()
+    }
+
+    /// A hypercube always contains at least one point.
+    pub fn is_empty(&self) -> bool { false }
+
+    /// Returns the $i$-th point in index order, spreading the bits of $i$ over the free
+    /// variables from the lowest one up.
+    pub fn point(&self, i: usize) -> usize {
+        assert!(i < self.len(), "point {i} out of range");
+        let mut point = self.fixed_bits;
+        let mut rest = i;
+        let mut var = 0;
+        while rest != 0 {
+            if self.fixed_mask >> var & 1 == 0 {
+                point |= (rest & 1) << var;
+                rest >>= 1;
+            }
+            var += 1;
+        }
+        point
+    }
+
+    /// Returns the variable behind bit $k$ of the index, the $k$-th free one.
+    fn free_var(&self, k: usize) -> usize {
+        (0..self.num_vars).filter(|var| self.fixed_mask >> var & 1 == 0).nth(k).unwrap()
+    }
+
+    /// Iterates over all points in index order.
+    pub fn points(&self) -> Points {
+        Points { cube: *self, next: 0, end: self.len() }
+    }
+
+    /// Iterates over all points in Gray-code order.
+    pub fn gray_code(&self) -> GrayCode {
+        self.points().gray_code()
+    }
+
+    /// Splits the points into `n` contiguous chunks of nearly equal size, one per worker.
+    pub fn chunks(&self, n: usize) -> impl Iterator<Item = Points> {
+        assert!(n > 0, "no chunks");
+        let (cube, len) = (*self, self.len());
+        (0..n).map(move |k| Points { cube, next: k * len / n, end: (k + 1) * len / n })
+    }
+}
+
+/// The points of a [`Hypercube`] with index in `next..end`, in index order.
+#[derive(Debug, Clone)]
+pub struct Points {
+    cube: Hypercube,
+    next: usize,
+    end: usize,
+}
+
+impl Points {
+    /// Splits off the points from `mid` on, `mid` counting from the current position.
+    pub fn split_at(self, mid: usize) -> (Self, Self) {
+        let mid = (self.next + mid).min(self.end);
+        (Self { end: mid, ..self.clone() }, Self { next: mid, ..self })
+    }
+
+    /// Walks the same range of indices in Gray-code order instead.
+    pub fn gray_code(self) -> GrayCode {
+        GrayCode { cube: self.cube, start: self.next, next: self.next, end: self.end }
+    }
+}
+
+impl Iterator for Points {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.next == self.end {
+            return None;
+        }
+        self.next += 1;
+        Some(self.cube.point(self.next - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.end - self.next, Some(self.end - self.next))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<usize> {
+        self.next = self.next.saturating_add(n).min(self.end);
+        self.next()
+    }
+}
+
+impl DoubleEndedIterator for Points {
+    fn next_back(&mut self) -> Option<usize> {
+        if self.next == self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(self.cube.point(self.end))
+    }
+}
+
+impl ExactSizeIterator for Points {}
+
+/// The points of a [`Hypercube`] in Gray-code order, each with the variable flipped to
+/// reach it from the previous point, `None` for the first one, also in a chunk that starts
+/// past the first point.
+#[derive(Debug, Clone)]
+pub struct GrayCode {
+    cube: Hypercube,
+    start: usize,
+    next: usize,
+    end: usize,
+}
+
+impl Iterator for GrayCode {
+    type Item = (usize, Option<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == self.end {
+            return None;
+        }
+        let i = self.next;
+        self.next += 1;
+        let point = self.cube.point(i ^ (i >> 1));
+        // $i - 1$ and $i$ have Gray codes differing in the lowest set bit of $i$
+        let flipped = if i == self.start {
+            None
+        } else {
+            Some(self.cube.free_var(i.trailing_zeros() as usize))
+        };
+        Some((point, flipped))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.end - self.next, Some(self.end - self.next))
+    }
+}
+
+impl ExactSizeIterator for GrayCode {}
+
+/// Iterates over the points of $\{0,1\}^n$ as field elements, bit $i$ of the index giving
+/// $x_i$.
+pub struct BooleanHypercube<F: Field> {
+    points: Points,
+    __f: PhantomData<F>,
+}
+
+impl<F: Field> BooleanHypercube<F> {
+
+    pub fn new(n: u32) -> Self {
+        Self {
+            points: Hypercube::new(n as usize).points(),
+            __f: PhantomData,
+        }
+    }
+
+    fn to_point(&self, mask: usize) -> Vec<F> {
+        (0..self.points.cube.num_vars)
+            .map(|i| match mask >> i & 1 {
+                0 => F::zero(),
+                _ => F::one(),
+            })
+            .collect()
+    }
+}
+
+impl<F: Field> Iterator for BooleanHypercube<F> {
+    type Item = Vec<F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mask = self.points.next()?;
+        Some(self.to_point(mask))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.points.size_hint()
+    }
+}
+
+impl<F: Field> ExactSizeIterator for BooleanHypercube<F> {}
+
+/// Evaluates a sparse polynomial at a point of the hypercube, updating the value in time
+/// proportional to the terms of $x_i$ whenever $x_i$ flips.
+///
+/// On $\{0,1\}^n$ a term contributes its coefficient exactly when all its variables are 1,
+/// so it is enough to track how many of them are still 0.
+#[derive(Debug, Clone)]
+pub struct IncrementalEvaluator<F: Field> {
+    point: usize,
+    value: F,
+    coeffs: Vec<F>,
+    /// The number of variables of every term that are 0 at `point`.
+    zeros: Vec<usize>,
+    /// The terms every variable occurs in.
+    occurrences: Vec<Vec<usize>>,
+}
+
+impl<F: Field> IncrementalEvaluator<F> {
+    /// Starts at `point`, evaluating `g` there from scratch.
+    pub fn new(g: &multivariate::SparsePolynomial<F, SparseTerm>, point: usize) -> Self {
+        let mut occurrences = vec![Vec::new(); g.num_vars];
+        let mut coeffs = Vec::with_capacity(g.terms.len());
+        let mut zeros = Vec::with_capacity(g.terms.len());
+        let mut value = F::zero();
+        for (t, (coeff, term)) in g.terms.iter().enumerate() {
+            for (var, _) in term.iter() {
+                occurrences[*var].push(t);
+            }
+            let z = term.iter().filter(|(var, _)| point >> var & 1 == 0).count();
+            if z == 0 {
+                value += coeff;
+            }
+            coeffs.push(*coeff);
+            zeros.push(z);
+        }
+        Self { point, value, coeffs, zeros, occurrences }
+    }
+
+    pub fn point(&self) -> usize { self.point }
+
+    /// Returns $g$ at the current point.
+    pub fn value(&self) -> F { self.value }
+
+    /// Flips $x_{var}$ and returns the value at the new point.
+    pub fn flip(&mut self, var: usize) -> F {
+        self.point ^= 1 << var;
+        let set = self.point >> var & 1 == 1;
+        for t in &self.occurrences[var] {
+            if set {
+                self.zeros[*t] -= 1;
+                if self.zeros[*t] == 0 {
+                    self.value += self.coeffs[*t];
+                }
+            } else {
+                if self.zeros[*t] == 0 {
+                    self.value -= self.coeffs[*t];
+                }
+                self.zeros[*t] += 1;
+            }
+        }
+        self.value
+    }
+}
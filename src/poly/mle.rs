@@ -0,0 +1,166 @@
+//! Multilinear extensions of functions on the Boolean hypercube.
+use std::fmt;
+use std::sync::Arc;
+
+use ark_ff::Field;
+use ark_poly::univariate;
+
+use super::eval::{eq_table, evaluate_table, evaluate_table_at_points};
+use super::{HypercubeEvaluation, OracleEvaluation, PartialEvaluation, UnivariateRestriction};
+use crate::error::Error;
+
+/// The multilinear extension $\tilde f$ of a function $f: \{0,1\}^n \to F$.
+///
+/// Points of the hypercube are indexed with bit $i$ giving $x_i$, the order the
+/// [`crate::sumcheck_ml`] and [`crate::sumcheck_pml`] provers fold in. The values of $f$
+/// are either stored as a table or computed on demand from a function of the index.
+#[derive(Clone)]
+pub struct Mle<F: Field> {
+    num_vars: usize,
+    source: Source<F>,
+}
+
+#[derive(Clone)]
+enum Source<F> {
+    Table(Vec<F>),
+    Function(Arc<dyn Fn(usize) -> F + Send + Sync>),
+}
+
+impl<F: Field> Mle<F> {
+    /// The extension of the table $f(0), ..., f(2^n - 1)$.
+    pub fn from_evaluations(num_vars: usize, evaluations: Vec<F>) -> Result<Self, Error<F>> {
+        if evaluations.len() != 1 << num_vars {
+            return Err(Error::WrongTableLength(1 << num_vars, evaluations.len()));
+        }
+        Ok(Self { num_vars, source: Source::Table(evaluations) })
+    }
+
+    /// The extension of `f`, evaluated over the whole hypercube right away.
+    pub fn from_fn(num_vars: usize, f: impl FnMut(usize) -> F) -> Self {
+        Self { num_vars, source: Source::Table((0..1 << num_vars).map(f).collect()) }
+    }
+
+    /// The extension of `f` taking the point as $x_0, ..., x_{n-1}$, evaluated over the
+    /// whole hypercube right away.
+    pub fn from_boolean_fn(num_vars: usize, f: impl Fn(&[bool]) -> F) -> Self {
+        let mut point = vec![false; num_vars];
+        Self::from_fn(num_vars, |b| {
+            for (i, x_i) in point.iter_mut().enumerate() {
+                *x_i = b >> i & 1 == 1;
+            }
+            f(&point)
+        })
+    }
+
+    /// The extension of `f`, calling `f` only when a value is needed. Every evaluation at a
+    /// point outside the hypercube calls it $2^n$ times.
+    pub fn lazy(num_vars: usize, f: impl Fn(usize) -> F + Send + Sync + 'static) -> Self {
+        Self { num_vars, source: Source::Function(Arc::new(f)) }
+    }
+
+    /// Returns $f(b)$ for the point with index $b$.
+    pub fn value(&self, b: usize) -> F {
+        match &self.source {
+            Source::Table(table) => table[b],
+            Source::Function(f) => f(b),
+        }
+    }
+
+    /// Evaluates `f` everywhere, so later calls do not go through the function again.
+    pub fn materialize(self) -> Self {
+        match self.source {
+            Source::Table(_) => self,
+            Source::Function(ref f) => Self::from_fn(self.num_vars, f.as_ref()),
+        }
+    }
+
+    fn into_table(self) -> Vec<F> {
+        match self.materialize().source {
+            Source::Table(table) => table,
+            Source::Function(_) => unreachable!(),
+        }
+    }
+}
+
+impl<F: Field> fmt::Debug for Mle<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.source {
+            Source::Table(table) => f.debug_struct("Mle").field("num_vars", &self.num_vars).field("table", table).finish(),
+            Source::Function(_) => f.debug_struct("Mle").field("num_vars", &self.num_vars).finish_non_exhaustive(),
+        }
+    }
+}
+
+impl<F: Field> OracleEvaluation<F> for Mle<F> {
+    /// Evaluates $\tilde f$ at `point`, `None` if it has the wrong number of coordinates.
+    fn evaluate(&self, point: &[F]) -> Option<F> {
+        if point.len() != self.num_vars {
+            return None;
+        }
+        Some(match &self.source {
+            Source::Table(table) => evaluate_table(table, point),
+            Source::Function(f) => eq_table(point).into_iter().enumerate().map(|(b, eq)| eq * f(b)).sum(),
+        })
+    }
+
+    fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    fn degrees(&self) -> Vec<usize> {
+        vec![1; self.num_vars]
+    }
+
+    /// Evaluates all extensions against a single eq table.
+    fn evaluate_many(polys: &[Self], point: &[F]) -> Option<Vec<F>> {
+        if polys.iter().any(|p| p.num_vars != point.len()) {
+            return None;
+        }
+        let eq = eq_table(point);
+        Some(polys.iter().map(|p| eq.iter().enumerate().map(|(b, eq)| *eq * p.value(b)).sum()).collect())
+    }
+
+    /// Evaluates $\tilde f$ at every point, folding its table in one scratch buffer.
+    fn evaluate_at_points(&self, points: &[Vec<F>]) -> Option<Vec<F>> {
+        if points.iter().any(|r| r.len() != self.num_vars) {
+            return None;
+        }
+        Some(match &self.source {
+            Source::Table(table) => evaluate_table_at_points(table, points),
+            Source::Function(_) => evaluate_table_at_points(&self.to_evaluations(), points),
+        })
+    }
+}
+
+impl<F: Field> HypercubeEvaluation<F> for Mle<F> {
+    fn to_evaluations(&self) -> Vec<F> {
+        self.clone().into_table()
+    }
+}
+
+impl<F: Field> PartialEvaluation<F> for Mle<F> {
+    fn fix_variables(&self, partial_point: &[F]) -> Self {
+        let mut table = self.clone().into_table();
+        for r in partial_point {
+            // bind the lowest variable, $x_0$, the same fold as the provers
+            for b in 0..table.len() / 2 {
+                table[b] = table[b << 1] + (table[(b << 1) + 1] - table[b << 1]) * r;
+            }
+            table.truncate(table.len() / 2);
+        }
+        Self { num_vars: self.num_vars - partial_point.len(), source: Source::Table(table) }
+    }
+}
+
+impl<F: Field> UnivariateRestriction<F> for Mle<F> {
+    fn to_univariate(&self) -> univariate::SparsePolynomial<F> {
+        // $\tilde f$ is linear in $x_0$: $a + (b - a) X$ with $a$ and $b$ the sums over
+        // the points with $x_0 = 0$ and $x_0 = 1$
+        let (mut a, mut b) = (F::zero(), F::zero());
+        for k in 0..1usize << self.num_vars.saturating_sub(1) {
+            a += self.value(k << 1);
+            b += self.value((k << 1) + 1);
+        }
+        univariate::SparsePolynomial::from_coefficients_vec(vec![(0, a), (1, b - a)])
+    }
+}
@@ -0,0 +1,220 @@
+//! The polynomial interface shared by every protocol.
+//!
+//! The protocols need different views of $g$, so the interface is split into layers:
+//!
+//! - [`OracleEvaluation`]: evaluating $g$ at a single point, all the verifiers need.
+//! - [`HypercubeEvaluation`]: the table of $g$ over $\{0,1\}^n$, which the
+//!   [`crate::sumcheck_ml`] and [`crate::sumcheck_pml`] provers fold round by round.
+//! - [`PartialEvaluation`]: fixing the leading variables of $g$ to field elements.
+//! - [`UnivariateRestriction`]: summing out all but the first variable, which the
+//!   [`crate::sumcheck_naive`] prover sends every round.
+//!
+//! [`SumCheckPolynomial`] is every layer at once and is implemented for
+//! [`multivariate::SparsePolynomial`] and [`Mle`], so one polynomial works with every prover.
+#[cfg(test)]
+mod test;
+mod eval;
+mod hypercube;
+mod mle;
+mod transform;
+
+pub use eval::{eq_table, evaluate_table, evaluate_table_at_points, evaluate_tables};
+pub use hypercube::{BooleanHypercube, GrayCode, Hypercube, IncrementalEvaluator, Points};
+pub use mle::Mle;
+pub use transform::{mobius_transform, zeta_transform};
+
+use ark_ff::{Field, Zero};
+use ark_poly::{
+    multivariate::{self, SparseTerm, Term},
+    DenseMVPolynomial,
+    univariate, Polynomial,
+};
+use ark_std::rand::Rng;
+
+pub trait RngF<F> {
+    fn draw(&mut self) -> F;
+}
+
+impl<F: Field, T: Rng> RngF<F> for T {
+    fn draw(&mut self) -> F {
+        F::rand(self)
+    }
+}
+
+/// The order in which the [`crate::sumcheck_ml`] and [`crate::sumcheck_pml`] provers bind
+/// the variables of $g$, one per round. Prover and verifier have to agree on it.
+/// [`crate::sumcheck_naive`] always binds low to high.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BindingOrder {
+    /// $x_0$ first, folding neighbouring entries of the table.
+    #[default]
+    LowToHigh,
+
+    /// $x_{n-1}$ first, folding the upper half of the table onto the lower one. Both halves
+    /// are read sequentially, which is friendlier to the cache for large tables.
+    HighToLow,
+}
+
+impl BindingOrder {
+    /// Returns the variable bound in round `j` of `num_vars`.
+    pub fn variable(&self, j: usize, num_vars: usize) -> usize {
+        match self {
+            BindingOrder::LowToHigh => j,
+            BindingOrder::HighToLow => num_vars - 1 - j,
+        }
+    }
+
+    /// Arranges the challenges $r_0, r_1, ...$ of the rounds as a point $(x_0, x_1, ...)$.
+    pub fn point<F: Copy>(&self, challenges: &[F]) -> Vec<F> {
+        match self {
+            BindingOrder::LowToHigh => challenges.to_vec(),
+            BindingOrder::HighToLow => challenges.iter().rev().copied().collect(),
+        }
+    }
+
+    /// Returns the indices of the entries with the bound variable at 0 and at 1 that fold
+    /// into entry `b`, for a table whose live part has `2 * half` entries.
+    pub(crate) fn pair(&self, b: usize, half: usize) -> (usize, usize) {
+        match self {
+            BindingOrder::LowToHigh => (b << 1, (b << 1) + 1),
+            BindingOrder::HighToLow => (b, b + half),
+        }
+    }
+}
+
+/// Oracle access to a polynomial $g$ in $n$ variables.
+pub trait OracleEvaluation<F: Field> {
+    /// Evaluates `self` at a given point
+    fn evaluate(&self, point: &[F]) -> Option<F>;
+
+    /// Returns the number of variables in `self`
+    fn num_vars(&self) -> usize;
+
+    /// Returns the degree of `self` in each of its variables
+    fn degrees(&self) -> Vec<usize>;
+
+    /// Evaluates every polynomial of `polys` at the same point, `None` if any of them
+    /// cannot be evaluated there. Implementations share work across the batch where they can.
+    fn evaluate_many(polys: &[Self], point: &[F]) -> Option<Vec<F>>
+    where
+        Self: Sized,
+    {
+        polys.iter().map(|p| p.evaluate(point)).collect()
+    }
+
+    /// Evaluates `self` at every point, `None` if it cannot be evaluated at one of them.
+    /// Implementations share work across the points where they can.
+    fn evaluate_at_points(&self, points: &[Vec<F>]) -> Option<Vec<F>> {
+        points.iter().map(|point| self.evaluate(point)).collect()
+    }
+}
+
+pub trait HypercubeEvaluation<F: Field>: OracleEvaluation<F> {
+    /// Returns a list of evaluations over the entire BooleanHypercube domain
+    fn to_evaluations(&self) -> Vec<F>;
+}
+
+pub trait PartialEvaluation<F: Field>: OracleEvaluation<F> + Sized {
+    /// Reduce the number of variables in `Self` by fixing a
+    /// `partial_point.len()` variables at `partial_point`.
+    fn fix_variables(&self, partial_point: &[F]) -> Self;
+}
+
+pub trait UnivariateRestriction<F: Field>: PartialEvaluation<F> {
+    /// Returns $\sum_{x_1,...,x_{n-1} \in \{0,1\}} g(X, x_1, ..., x_{n-1})$.
+    fn to_univariate(&self) -> univariate::SparsePolynomial<F>;
+}
+
+/// A polynomial every protocol can run on.
+pub trait SumCheckPolynomial<F: Field>: HypercubeEvaluation<F> + UnivariateRestriction<F> {}
+
+impl<F: Field, P: HypercubeEvaluation<F> + UnivariateRestriction<F>> SumCheckPolynomial<F> for P {}
+
+impl<F: Field> OracleEvaluation<F> for multivariate::SparsePolynomial<F, SparseTerm> {
+    fn evaluate(&self, point: &[F]) -> Option<F> {
+        Some(Polynomial::evaluate(self, &point.to_owned()))
+    }
+
+    fn num_vars(&self) -> usize {
+        DenseMVPolynomial::num_vars(self)
+    }
+
+    fn degrees(&self) -> Vec<usize> {
+        let mut degrees = vec![0; DenseMVPolynomial::num_vars(self)];
+        for (_, term) in self.terms() {
+            for (var, power) in term.iter() {
+                degrees[*var] = degrees[*var].max(*power);
+            }
+        }
+        degrees
+    }
+}
+
+impl<F: Field> HypercubeEvaluation<F> for multivariate::SparsePolynomial<F, SparseTerm> {
+    fn to_evaluations(&self) -> Vec<F> {
+        // $x^k = x$ on $\{0,1\}$, so every term lands on the multilinear monomial of its
+        // variables and the table follows from the coefficients by a zeta transform.
+        let mut table = vec![F::zero(); 1 << DenseMVPolynomial::num_vars(self)];
+        for (coeff, term) in self.terms() {
+            let mask = term.iter().fold(0usize, |mask, (var, _)| mask | 1 << var);
+            table[mask] += coeff;
+        }
+        zeta_transform(&mut table);
+        table
+    }
+}
+
+impl<F: Field> PartialEvaluation<F> for multivariate::SparsePolynomial<F, SparseTerm> {
+    fn fix_variables(&self, partial_point: &[F]) -> Self {
+        let mut res = Self::zero();
+        let num_vars = DenseMVPolynomial::num_vars(self);
+        let mut full_point = partial_point.to_vec();
+        full_point.append(&mut vec![F::one(); num_vars - partial_point.len()]);
+
+        for (coeff, term) in self.terms() {
+            let mut eval = term.evaluate(&full_point);
+            eval *= coeff;
+            let new_term = SparseTerm::new(
+                term.iter()
+                    .filter(|(var, _)| *var >= partial_point.len())
+                    .map(|(var, power)| (var - partial_point.len(), *power))
+                    .collect(),
+            );
+            let poly = multivariate::SparsePolynomial {
+                num_vars: num_vars - partial_point.len(),
+                terms: vec![(eval, new_term)],
+            };
+
+            res += &poly;
+        }
+
+        res
+    }
+}
+
+impl<F: Field> UnivariateRestriction<F> for multivariate::SparsePolynomial<F, SparseTerm> {
+    fn to_univariate(&self) -> univariate::SparsePolynomial<F> {
+        let mut res = univariate::SparsePolynomial::zero();
+
+        for p in BooleanHypercube::new((DenseMVPolynomial::num_vars(self) - 1) as u32) {
+            let mut point = vec![F::one()];
+            point.extend_from_slice(&p);
+            let mut r = univariate::SparsePolynomial::zero();
+
+            for (coeff, term) in self.terms() {
+                let mut eval = term.evaluate(&point);
+                let power = term
+                    .iter()
+                    .find(|(variable, _power)| *variable == 0)
+                    .map(|(_variable, power)| *power)
+                    .unwrap_or(0);
+
+                eval *= coeff;
+                r += &univariate::SparsePolynomial::from_coefficients_slice(&[(power, eval)]);
+            }
+
+            res += &r;
+        }
+        res
+    }
+}
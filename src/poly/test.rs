@@ -0,0 +1,209 @@
+use ark_ff::UniformRand;
+use ark_poly::{multivariate::{self, SparseTerm, Term}, univariate, DenseMVPolynomial};
+use ark_std::test_rng;
+
+use crate::error::Error;
+use crate::fields::Fp97;
+use crate::oracle::Product;
+use crate::parser::parse_polynomial;
+use crate::poly::{
+    evaluate_table, evaluate_table_at_points, evaluate_tables, mobius_transform, zeta_transform, BooleanHypercube, Hypercube, HypercubeEvaluation, IncrementalEvaluator, Mle,
+    OracleEvaluation, PartialEvaluation, UnivariateRestriction,
+};
+use crate::protocol::run_protocol;
+use crate::{sumcheck_ml, sumcheck_naive, sumcheck_pml};
+
+#[test]
+fn test_layers(){
+    let fp = |n: u64| Fp97::from(n);
+    let g = parse_polynomial::<Fp97>("2*x0^3 + x0*x2 + x1*x2").unwrap();
+
+    let points: Vec<Vec<Fp97>> = BooleanHypercube::new(3).collect();
+    assert_eq!(points.len(), 8);
+    assert_eq!(points[1], vec![fp(1), fp(0), fp(0)]);
+    assert_eq!(
+        g.to_evaluations(),
+        points.iter().map(|p| g.evaluate(p).unwrap()).collect::<Vec<_>>()
+    );
+    assert_eq!(g.degrees(), vec![3, 1, 1]);
+
+    // fixing $x_0 = 5$ leaves $250 + 5 x_1 + x_0 x_1$ in the shifted variables
+    let h = g.fix_variables(&[fp(5)]);
+    assert_eq!(OracleEvaluation::num_vars(&h), 2);
+    assert_eq!(h.evaluate(&[fp(3), fp(7)]), g.evaluate(&[fp(5), fp(3), fp(7)]));
+
+    // $\sum_{x_1, x_2} g(X, x_1, x_2) = 8 X^3 + 2 X + 1$
+    let expected = univariate::SparsePolynomial::from_coefficients_vec(vec![(0, fp(1)), (1, fp(2)), (3, fp(8))]);
+    assert_eq!(g.to_univariate(), expected);
+}
+
+#[test]
+fn test_transforms(){
+    let rng = &mut test_rng();
+    for num_vars in 0..6 {
+        let coeffs: Vec<Fp97> = (0..1 << num_vars).map(|_| Fp97::rand(rng)).collect();
+        let g = multivariate::SparsePolynomial::from_coefficients_vec(
+            num_vars,
+            coeffs
+                .iter()
+                .enumerate()
+                .map(|(mask, c)| (*c, SparseTerm::new((0..num_vars).filter(|i| mask >> i & 1 == 1).map(|i| (i, 1)).collect())))
+                .collect(),
+        );
+
+        let mut table = coeffs.clone();
+        zeta_transform(&mut table);
+        let points = BooleanHypercube::new(num_vars as u32);
+        assert_eq!(table, points.map(|p| g.evaluate(&p).unwrap()).collect::<Vec<_>>());
+        assert_eq!(g.to_evaluations(), table);
+
+        mobius_transform(&mut table);
+        assert_eq!(table, coeffs);
+    }
+
+    // higher powers collapse onto the multilinear monomial of their variables
+    let g = parse_polynomial::<Fp97>("2*x0^3 + x0*x2^2 + 7*x1").unwrap();
+    let points = BooleanHypercube::new(3);
+    assert_eq!(g.to_evaluations(), points.map(|p| g.evaluate(&p).unwrap()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_hypercube(){
+    let cube = Hypercube::new(4);
+    assert_eq!(cube.points().len(), 16);
+    assert!(cube.points().eq(0..16));
+    assert_eq!(cube.points().next_back(), Some(15));
+    assert_eq!(BooleanHypercube::<Fp97>::new(4).len(), 16);
+
+    // $x_1 = 1$, $x_3 = 0$ leaves $x_0$ and $x_2$ free
+    let sub = cube.fix(1, true).fix(3, false);
+    assert_eq!(sub.free_vars(), 2);
+    assert_eq!(sub.points().collect::<Vec<_>>(), vec![0b0010, 0b0011, 0b0110, 0b0111]);
+    assert_eq!(sub.point(2), 0b0110);
+
+    // chunks and splits partition the points in order
+    let chunks: Vec<usize> = cube.chunks(3).flatten().collect();
+    assert_eq!(chunks, (0..16).collect::<Vec<_>>());
+    let (low, high) = sub.points().split_at(1);
+    assert_eq!((low.len(), high.len()), (1, 3));
+
+    for cube in [cube, sub] {
+        let mut prev = None;
+        let mut seen = [false; 16];
+        for (point, flipped) in cube.gray_code() {
+            seen[point] = true;
+            match (prev, flipped) {
+                (None, None) => {}
+                (Some(prev), Some(var)) => assert_eq!(prev ^ point, 1 << var),
+                _ => panic!("flip reported at the wrong point"),
+            }
+            prev = Some(point);
+        }
+        assert_eq!(seen.iter().filter(|s| **s).count(), cube.len());
+    }
+
+    // every chunk starts without a flip, as the point before it is not in the chunk
+    for chunk in Hypercube::new(4).chunks(3) {
+        let mut gray = chunk.gray_code();
+        assert!(matches!(gray.next(), Some((_, None))));
+        assert!(gray.all(|(_, flipped)| flipped.is_some()));
+    }
+}
+
+#[test]
+fn test_incremental_evaluation(){
+    let g = parse_polynomial::<Fp97>("2*x0^3 + x0*x2 + 5*x1*x2*x3 + 7").unwrap();
+    let to_point = |mask: usize| (0..4).map(|i| Fp97::from((mask >> i & 1) as u8)).collect::<Vec<_>>();
+
+    let mut gray = Hypercube::new(4).gray_code();
+    let (start, _) = gray.next().unwrap();
+    let mut eval = IncrementalEvaluator::new(&g, start);
+    assert_eq!(Some(eval.value()), g.evaluate(&to_point(start)));
+    for (point, flipped) in gray {
+        let value = eval.flip(flipped.unwrap());
+        assert_eq!(eval.point(), point);
+        assert_eq!(Some(value), g.evaluate(&to_point(point)));
+    }
+}
+
+#[test]
+fn test_mle(){
+    let rng = &mut test_rng();
+    let f = |b: usize| Fp97::from((b * b + 3) as u64);
+
+    let eager = Mle::from_fn(3, f);
+    let lazy = Mle::lazy(3, f);
+    let boolean = Mle::from_boolean_fn(3, |x: &[bool]| {
+        let b = x.iter().enumerate().map(|(i, x_i)| (*x_i as usize) << i).sum::<usize>();
+        f(b)
+    });
+    assert_eq!(eager.to_evaluations(), (0..8).map(f).collect::<Vec<_>>());
+    assert_eq!(lazy.to_evaluations(), eager.to_evaluations());
+    assert_eq!(boolean.to_evaluations(), eager.to_evaluations());
+    assert!(matches!(Mle::from_evaluations(3, vec![Fp97::from(1u8); 7]), Err(Error::WrongTableLength(8, 7))));
+
+    // the extension agrees with the sparse polynomial having the same table
+    let g = parse_polynomial::<Fp97>("3*x0 + 5*x0*x1 + 7*x1*x2 + 11").unwrap();
+    let mle = Mle::from_evaluations(3, g.to_evaluations()).unwrap();
+    let point: Vec<Fp97> = (0..3).map(|_| Fp97::rand(rng)).collect();
+    assert_eq!(mle.evaluate(&point), g.evaluate(&point));
+    assert_eq!(Mle::lazy(3, move |b| mle.value(b)).evaluate(&point), g.evaluate(&point));
+    assert_eq!(eager.evaluate(&point[..2]), None);
+
+    let mle = Mle::from_evaluations(3, g.to_evaluations()).unwrap();
+    let fixed = mle.fix_variables(&point[..1]);
+    assert_eq!(fixed.evaluate(&point[1..]), g.evaluate(&point));
+    assert_eq!(mle.to_univariate(), g.to_univariate());
+    assert_eq!(mle.degrees(), vec![1, 1, 1]);
+}
+
+#[test]
+fn test_mle_provers(){
+    let rng = &mut test_rng();
+    let f = |b: usize| Fp97::from((7 * b + 1) as u64);
+    let g = Mle::lazy(3, f);
+
+    let prover = sumcheck_ml::prover::Prover::new(g.clone()).unwrap();
+    let outcome = run_protocol(prover, |claim| sumcheck_ml::verifier::Verifier::with_options(g.clone(), claim, sumcheck_ml::verifier::VerifierOptions::default().allow_insecure()).unwrap(), rng).unwrap();
+    assert!(outcome.accepted);
+    assert_eq!(outcome.transcript.claim, (0..8).map(f).sum());
+
+    let factors = vec![g.clone(), Mle::from_fn(3, |b| Fp97::from(b as u64))];
+    let prover = sumcheck_pml::prover::Prover::new(factors.clone()).unwrap();
+    let outcome = run_protocol(prover, |claim| sumcheck_pml::verifier::Verifier::with_options(Product::new(factors).unwrap(), claim, sumcheck_pml::verifier::VerifierOptions::default().allow_insecure()).unwrap(), rng).unwrap();
+    assert!(outcome.accepted);
+
+    let prover = sumcheck_naive::Prover::new(g.clone()).unwrap();
+    let verifier = |claim| {
+        let mut verifier = sumcheck_naive::Verifier::with_options(g, sumcheck_naive::VerifierOptions::default().allow_insecure()).unwrap();
+        verifier.set_c_1(claim);
+        verifier
+    };
+    assert!(run_protocol(prover, verifier, rng).unwrap().accepted);
+}
+
+#[test]
+fn test_batch_evaluation(){
+    let rng = &mut test_rng();
+    let tables: Vec<Vec<Fp97>> = (0..4).map(|_| (0..16).map(|_| Fp97::rand(rng)).collect()).collect();
+    let points: Vec<Vec<Fp97>> = (0..5).map(|_| (0..4).map(|_| Fp97::rand(rng)).collect()).collect();
+
+    let one_point = evaluate_tables(&tables, &points[0]);
+    for (table, value) in tables.iter().zip(&one_point) {
+        assert_eq!(evaluate_table(table, &points[0]), *value);
+    }
+    let many_points = evaluate_table_at_points(&tables[0], &points);
+    for (r, value) in points.iter().zip(&many_points) {
+        assert_eq!(evaluate_table(&tables[0], r), *value);
+    }
+
+    let mles: Vec<Mle<Fp97>> = tables.iter().map(|t| Mle::from_evaluations(4, t.clone()).unwrap()).collect();
+    assert_eq!(Mle::evaluate_many(&mles, &points[0]), Some(one_point));
+    assert_eq!(mles[0].evaluate_at_points(&points), Some(many_points));
+    assert_eq!(Mle::evaluate_many(&mles, &points[0][..3]), None);
+
+    // the default falls back to one evaluation per polynomial
+    let g = vec![parse_polynomial::<Fp97>("3*x0*x1 + x2").unwrap(), parse_polynomial::<Fp97>("x0 + x1*x2").unwrap()];
+    let values = OracleEvaluation::evaluate_many(&g, &points[0][..3]).unwrap();
+    assert_eq!(values[1], g[1].evaluate(&points[0][..3]).unwrap());
+}
@@ -0,0 +1,47 @@
+//! Conversions between the coefficients of a multilinear polynomial and its evaluations
+//! over the Boolean hypercube.
+//!
+//! Both sides are vectors of length $2^n$ indexed by a bit mask. Entry $S$ of the
+//! coefficient vector is the coefficient of $\prod_{i \in S} x_i$, entry $S$ of the
+//! evaluation vector is the value at the point with $x_i = 1$ exactly for $i \in S$, the
+//! order of [`super::BooleanHypercube`]. Since $g(S) = \sum_{T \subseteq S} c_T$, the
+//! evaluations are the subset sums of the coefficients (the zeta transform) and the
+//! coefficients are recovered by Möbius inversion. Both take $O(n 2^n)$ field additions
+//! instead of evaluating every term at every point.
+use ark_ff::Field;
+
+/// Turns the coefficients of a multilinear polynomial into its evaluations over the
+/// hypercube, in place.
+///
+/// Panics unless `values.len()` is a power of two.
+pub fn zeta_transform<F: Field>(values: &mut [F]) {
+    assert!(values.len().is_power_of_two(), "length {} is not a power of two", values.len());
+    let mut half = 1;
+    while half < values.len() {
+        for chunk in values.chunks_mut(half << 1) {
+            let (low, high) = chunk.split_at_mut(half);
+            for (h, l) in high.iter_mut().zip(low.iter()) {
+                *h += l;
+            }
+        }
+        half <<= 1;
+    }
+}
+
+/// Turns the evaluations of a multilinear polynomial over the hypercube into its
+/// coefficients, in place. The inverse of [`zeta_transform`].
+///
+/// Panics unless `values.len()` is a power of two.
+pub fn mobius_transform<F: Field>(values: &mut [F]) {
+    assert!(values.len().is_power_of_two(), "length {} is not a power of two", values.len());
+    let mut half = 1;
+    while half < values.len() {
+        for chunk in values.chunks_mut(half << 1) {
+            let (low, high) = chunk.split_at_mut(half);
+            for (h, l) in high.iter_mut().zip(low.iter()) {
+                *h -= l;
+            }
+        }
+        half <<= 1;
+    }
+}
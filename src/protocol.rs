@@ -0,0 +1,213 @@
+use ark_ff::Field;
+use ark_poly::univariate;
+use ark_std::rand::Rng;
+
+use crate::error::Error;
+use crate::oracle::EvaluationOracle;
+use crate::poly::SumCheckPolynomial;
+use crate::round_poly::{RoundMessage, RoundPolynomial};
+use crate::{sumcheck_ml, sumcheck_naive, sumcheck_pml};
+
+/// The round interface shared by the provers of every protocol.
+pub trait SumcheckProver<F: Field> {
+    /// The message sent to the verifier in every round.
+    type Message: RoundMessage<F>;
+
+    /// Get the value the prover claims equals the sum over the hypercube.
+    fn claim(&self) -> F;
+
+    /// Returns the number of variables of $g$.
+    fn num_vars(&self) -> usize;
+
+    /// Returns the number of rounds the prover plays, fewer than [`Self::num_vars`] in a
+    /// partial run.
+    fn rounds(&self) -> usize { self.num_vars() }
+
+    /// Perform $j$-th round, $r_{prev}$ is the challenge of round $j-1$ (ignored when $j=0$).
+    /// Fails unless $j$ is the next round.
+    fn round(&mut self, r_prev: F, j: usize) -> Result<Self::Message, Error<F>>;
+}
+
+/// Runs a prover the caller keeps, e.g. to take the residual of a partial run afterwards.
+impl<F: Field, P: SumcheckProver<F>> SumcheckProver<F> for &mut P {
+    type Message = P::Message;
+
+    fn claim(&self) -> F { (**self).claim() }
+
+    fn num_vars(&self) -> usize { (**self).num_vars() }
+
+    fn rounds(&self) -> usize { (**self).rounds() }
+
+    fn round(&mut self, r_prev: F, j: usize) -> Result<P::Message, Error<F>> { (**self).round(r_prev, j) }
+}
+
+impl<F: Field> SumcheckProver<F> for sumcheck_ml::prover::Prover<F> {
+    type Message = (F, F);
+
+    fn claim(&self) -> F { self.claim() }
+
+    fn num_vars(&self) -> usize { self.num_vars() }
+
+    fn rounds(&self) -> usize { self.num_rounds() }
+
+    fn round(&mut self, r_prev: F, j: usize) -> Result<(F, F), Error<F>> { self.round(r_prev, j) }
+}
+
+impl<F: Field> SumcheckProver<F> for sumcheck_pml::prover::Prover<F> {
+    type Message = Vec<F>;
+
+    fn claim(&self) -> F { self.claim() }
+
+    fn num_vars(&self) -> usize { self.num_vars() }
+
+    fn rounds(&self) -> usize { self.num_rounds() }
+
+    fn round(&mut self, r_prev: F, j: usize) -> Result<Vec<F>, Error<F>> { self.round(r_prev, j) }
+}
+
+impl<F: Field, P: SumCheckPolynomial<F>> SumcheckProver<F> for sumcheck_naive::Prover<F, P> {
+    type Message = univariate::SparsePolynomial<F>;
+
+    fn claim(&self) -> F { self.c_1() }
+
+    fn num_vars(&self) -> usize { self.num_vars() }
+
+    fn round(&mut self, r_prev: F, j: usize) -> Result<univariate::SparsePolynomial<F>, Error<F>> { self.round(r_prev, j) }
+}
+
+/// Result of one verifier round: a challenge for the prover, or the final verdict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundOutcome<F> {
+    Challenge(F),
+    Final(bool),
+}
+
+/// The round interface shared by the verifiers of every protocol.
+pub trait SumcheckVerifier<F: Field> {
+    /// The message received from the prover in every round.
+    type Message;
+
+    /// The error returned when a round message is rejected.
+    type Error;
+
+    /// Perform the next round on the prover's `message`, drawing the challenge from `rng`.
+    fn round<R: Rng>(&mut self, message: Self::Message, rng: &mut R) -> Result<RoundOutcome<F>, Self::Error>;
+}
+
+impl<F: Field, O: EvaluationOracle<F>> SumcheckVerifier<F> for sumcheck_ml::verifier::Verifier<F, O> {
+    type Message = (F, F);
+    type Error = Error<F>;
+
+    fn round<R: Rng>(&mut self, (p0, p1): (F, F), rng: &mut R) -> Result<RoundOutcome<F>, Self::Error> {
+        use sumcheck_ml::verifier::VerifierRoundResult;
+
+        Ok(match self.round(p0, p1, rng)? {
+            VerifierRoundResult::JthRound(r) => RoundOutcome::Challenge(r),
+            VerifierRoundResult::FinalRound(res) => RoundOutcome::Final(res),
+        })
+    }
+}
+
+impl<F: Field, O: EvaluationOracle<F>> SumcheckVerifier<F> for sumcheck_pml::verifier::Verifier<F, O> {
+    type Message = Vec<F>;
+    type Error = Error<F>;
+
+    fn round<R: Rng>(&mut self, p: Vec<F>, rng: &mut R) -> Result<RoundOutcome<F>, Self::Error> {
+        use sumcheck_pml::verifier::VerifierRoundResult;
+
+        Ok(match self.round(p, rng)? {
+            VerifierRoundResult::JthRound(r) => RoundOutcome::Challenge(r),
+            VerifierRoundResult::FinalRound(res, _) => RoundOutcome::Final(res),
+        })
+    }
+}
+
+impl<F: Field, O: EvaluationOracle<F>> SumcheckVerifier<F> for sumcheck_naive::Verifier<F, O> {
+    type Message = univariate::SparsePolynomial<F>;
+    type Error = Error<F>;
+
+    fn round<R: Rng>(&mut self, g_j: univariate::SparsePolynomial<F>, rng: &mut R) -> Result<RoundOutcome<F>, Self::Error> {
+        use sumcheck_naive::VerifierRoundResult;
+
+        Ok(match self.round(g_j, rng)? {
+            VerifierRoundResult::JthRound(r) => RoundOutcome::Challenge(r),
+            VerifierRoundResult::FinalRound(res) => RoundOutcome::Final(res),
+        })
+    }
+}
+
+/// The public messages of a run: the claim, the round polynomials and the challenges
+/// answering all but the last of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transcript<F: Field> {
+    pub claim: F,
+    pub rounds: Vec<RoundPolynomial<F>>,
+    pub challenges: Vec<F>,
+}
+
+/// The result of [`run_protocol`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome<F: Field> {
+    /// Whether the verifier accepted.
+    pub accepted: bool,
+    pub transcript: Transcript<F>,
+}
+
+/// Runs `prover` against the verifier built by `verifier` from the prover's claim, drawing
+/// the challenges from `rng`. Returns the verifier's error if it rejects a message outright.
+pub fn run_protocol<F, P, V, R>(prover: P, verifier: impl FnOnce(F) -> V, rng: &mut R) -> Result<Outcome<F>, V::Error>
+where
+    F: Field,
+    P: SumcheckProver<F>,
+    V: SumcheckVerifier<F, Message = P::Message>,
+    V::Error: From<Error<F>>,
+    R: Rng,
+{
+    let claim = prover.claim();
+    run_protocol_on_claim(prover, claim, verifier, rng)
+}
+
+/// Same as [`run_protocol`] for `claim`, the public sum the prover has to convince the
+/// verifier of. The verifier and the transcript both take the claim from here, the
+/// prover's own is not consulted, so a prover whose sum differs is rejected in the first
+/// round.
+///
+/// A partial prover plays only its [`SumcheckProver::rounds`], against a verifier partial
+/// over the same rounds. The run then ends on the challenge of its last round, accepted if
+/// every round was consistent, and the residual claim is left to the next stage.
+pub fn run_protocol_on_claim<F, P, V, R>(mut prover: P, claim: F, verifier: impl FnOnce(F) -> V, rng: &mut R) -> Result<Outcome<F>, V::Error>
+where
+    F: Field,
+    P: SumcheckProver<F>,
+    V: SumcheckVerifier<F, Message = P::Message>,
+    V::Error: From<Error<F>>,
+    R: Rng,
+{
+    let mut verifier = verifier(claim);
+    let rounds = prover.rounds();
+    let mut transcript = Transcript {
+        claim,
+        rounds: Vec::with_capacity(rounds),
+        challenges: Vec::with_capacity(rounds),
+    };
+
+    // the first round ignores the challenge
+    let mut r_j = F::one();
+    for j in 0..rounds {
+        let message = prover.round(r_j, j)?;
+        transcript.rounds.push(message.to_round_polynomial()?);
+        match verifier.round(message, rng)? {
+            RoundOutcome::Challenge(r) => {
+                transcript.challenges.push(r);
+                r_j = r;
+            }
+            RoundOutcome::Final(accepted) => return Ok(Outcome { accepted, transcript }),
+        }
+    }
+
+    if rounds < prover.num_vars() {
+        return Ok(Outcome { accepted: true, transcript });
+    }
+    // the verifier asked for more rounds than the prover has
+    Err(Error::RoundOutOfOrder(rounds, rounds).into())
+}
@@ -0,0 +1,143 @@
+//! Parallel repetition for fields too small for a single sumcheck run to be sound.
+//!
+//! $k$ independent instances run in lockstep, each with its own fresh challenges, and the
+//! verifier accepts only if every instance does. A cheating prover has to fool all of
+//! them, so the soundness in bits grows linearly with $k$.
+#[cfg(test)]
+mod test;
+
+use ark_ff::Field;
+use ark_std::rand::Rng;
+
+use crate::error::Error;
+use crate::protocol::{RoundOutcome, SumcheckProver, SumcheckVerifier};
+use crate::soundness::SecurityConfig;
+
+/// Returns the number of repetitions of a protocol with `single_run_bits` of soundness
+/// needed to reach `target_bits`, or `None` if a single run has no soundness at all.
+pub fn repetitions_for(target_bits: f64, single_run_bits: f64) -> Option<usize> {
+    if single_run_bits <= 0.0 {
+        return None;
+    }
+    Some(((target_bits / single_run_bits).ceil() as usize).max(1))
+}
+
+/// Returns the number of repetitions needed to satisfy `config`, a single run if the
+/// configuration allows insecure runs.
+pub fn repetitions_for_config(config: &SecurityConfig, single_run_bits: f64) -> Option<usize> {
+    if config.allow_insecure {
+        return Some(1);
+    }
+    repetitions_for(config.min_bits, single_run_bits)
+}
+
+/// Runs $k$ copies of a prover, one per repetition.
+pub struct RepeatedProver<F: Field, P: SumcheckProver<F>> {
+    provers: Vec<P>,
+    claim: F,
+}
+
+impl<F: Field, P: SumcheckProver<F> + Clone> RepeatedProver<F, P> {
+    /// Clones `prover` into `k` repetitions. The provers of this crate share their
+    /// precomputed tables between clones, so they are built and held only once until the
+    /// first challenges tell the repetitions apart.
+    pub fn new(prover: P, k: usize) -> Result<Self, Error<F>> {
+        Self::from_provers(vec![prover; k])
+    }
+}
+
+impl<F: Field, P: SumcheckProver<F>> RepeatedProver<F, P> {
+    /// Runs the given provers as the repetitions. Fails unless there is at least one and
+    /// they all make the same claim.
+    pub fn from_provers(provers: Vec<P>) -> Result<Self, Error<F>> {
+        let claim = provers.first().ok_or(Error::EmptyInput)?.claim();
+        if let Some((i, prover)) = provers.iter().enumerate().find(|(_, p)| p.claim() != claim) {
+            return Err(Error::RepetitionClaimMismatch(i, claim, prover.claim()));
+        }
+        Ok(Self { provers, claim })
+    }
+
+    /// Get the value the prover claims equals the sum, shared by all repetitions.
+    pub fn claim(&self) -> F { self.claim }
+
+    pub fn num_vars(&self) -> usize { self.provers[0].num_vars() }
+
+    pub fn repetitions(&self) -> usize { self.provers.len() }
+
+    /// Perform $j$-th round of every repetition, `r_prev[i]` is the previous challenge of repetition $i$.
+    pub fn round(&mut self, r_prev: &[F], j: usize) -> Result<Vec<P::Message>, Error<F>> {
+        if r_prev.len() != self.provers.len() {
+            return Err(Error::WrongRepetitionCount(self.provers.len(), r_prev.len()));
+        }
+        self.provers
+            .iter_mut()
+            .zip(r_prev)
+            .map(|(prover, r)| prover.round(*r, j))
+            .collect()
+    }
+}
+
+/// Values returned by [`RepeatedVerifier`] on every step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepeatedRoundResult<F> {
+    /// The challenges for the next round, one per repetition.
+    JthRound(Vec<F>),
+    /// Whether every repetition accepted.
+    FinalRound(bool),
+}
+
+/// Runs $k$ verifiers in lockstep, one per repetition.
+pub struct RepeatedVerifier<F: Field, V: SumcheckVerifier<F>> {
+    verifiers: Vec<V>,
+    _f: std::marker::PhantomData<F>,
+}
+
+impl<F: Field, V: SumcheckVerifier<F>> RepeatedVerifier<F, V> {
+    /// Runs the given verifiers, all started from the same claim, as the repetitions.
+    /// Fails without any, as no repetitions would accept anything.
+    pub fn new(verifiers: Vec<V>) -> Result<Self, Error<F>> {
+        if verifiers.is_empty() {
+            return Err(Error::EmptyInput);
+        }
+        Ok(Self {
+            verifiers,
+            _f: std::marker::PhantomData,
+        })
+    }
+
+    /// Builds as many verifiers as `config` requires for a protocol with
+    /// `single_run_bits` of soundness, or `None` if no number of repetitions suffices.
+    pub fn for_security(config: &SecurityConfig, single_run_bits: f64, verifier: impl FnMut() -> V) -> Option<Self> {
+        let k = repetitions_for_config(config, single_run_bits)?;
+        // at least one repetition
+        Self::new(std::iter::repeat_with(verifier).take(k).collect()).ok()
+    }
+
+    pub fn repetitions(&self) -> usize { self.verifiers.len() }
+
+    /// Perform a round of every repetition, each drawing a fresh challenge from `rng`.
+    pub fn round<R: Rng>(&mut self, messages: Vec<V::Message>, rng: &mut R) -> Result<RepeatedRoundResult<F>, V::Error>
+    where
+        V::Error: From<Error<F>>,
+    {
+        if messages.len() != self.verifiers.len() {
+            return Err(Error::WrongRepetitionCount(self.verifiers.len(), messages.len()).into());
+        }
+
+        let mut challenges = Vec::with_capacity(self.verifiers.len());
+        let mut accept = true;
+        for (verifier, message) in self.verifiers.iter_mut().zip(messages) {
+            match verifier.round(message, rng)? {
+                RoundOutcome::Challenge(r) => challenges.push(r),
+                RoundOutcome::Final(res) => accept &= res,
+            }
+        }
+
+        // all repetitions share the number of rounds, so they finish together
+        if challenges.is_empty() {
+            Ok(RepeatedRoundResult::FinalRound(accept))
+        } else {
+            Ok(RepeatedRoundResult::JthRound(challenges))
+        }
+    }
+}
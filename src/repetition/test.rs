@@ -0,0 +1,105 @@
+use ark_ff::One;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use ark_std::test_rng;
+
+use crate::adversary::WrongClaim;
+use crate::error::Error;
+use crate::fields::Fp97;
+use crate::oracle::Product;
+use crate::parser::{parse_factors, parse_polynomial};
+use crate::protocol::{SumcheckProver, SumcheckVerifier};
+use crate::repetition::*;
+use crate::soundness::*;
+use crate::{sumcheck_ml, sumcheck_naive, sumcheck_pml};
+
+type Poly = ark_poly::multivariate::SparsePolynomial<Fp97, ark_poly::multivariate::SparseTerm>;
+
+fn run<P, V, R>(prover: &mut RepeatedProver<Fp97, P>, verifier: &mut RepeatedVerifier<Fp97, V>, rng: &mut R) -> bool
+where
+    P: SumcheckProver<Fp97>,
+    V: SumcheckVerifier<Fp97, Message = P::Message, Error = Error<Fp97>>,
+    R: ark_std::rand::Rng,
+{
+    let mut r = vec![Fp97::one(); prover.repetitions()];
+    for j in 0..prover.num_vars() {
+        match verifier.round(prover.round(&r, j).unwrap(), rng) {
+            Ok(RepeatedRoundResult::JthRound(challenges)) => r = challenges,
+            Ok(RepeatedRoundResult::FinalRound(res)) => return res,
+            Err(_) => return false,
+        }
+    }
+    false
+}
+
+#[test]
+fn test_repetition(){
+    assert_eq!(repetitions_for(128.0, 64.0), Some(2));
+    assert_eq!(repetitions_for(128.0, 60.0), Some(3));
+    assert_eq!(repetitions_for(128.0, -1.0), None);
+
+    let config = SecurityConfig::new(40.0);
+    let rng = &mut test_rng();
+
+    let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1 + 35*x1").unwrap();
+    let bits = ml_soundness_bits::<Fp97>(2);
+    let k = repetitions_for(config.min_bits, bits).unwrap();
+    assert_eq!(k, 8);
+    assert!(k as f64 * bits >= config.min_bits);
+
+    let mut prover = RepeatedProver::new(sumcheck_ml::prover::Prover::new(g.clone()).unwrap(), k).unwrap();
+    let claim = prover.claim();
+    let mut verifier = RepeatedVerifier::for_security(&config, bits, || sumcheck_ml::verifier::Verifier::with_options(g.clone(), claim, sumcheck_ml::verifier::VerifierOptions::default().allow_insecure()).unwrap()).unwrap();
+    assert_eq!(verifier.repetitions(), k);
+    assert!(run(&mut prover, &mut verifier, rng));
+
+    // a false claim survives a single run about once in 97 tries, but never all eight
+    for seed in 0..200u64 {
+        let honest = sumcheck_ml::prover::Prover::new(g.clone()).unwrap();
+        let delta = Fp97::from(seed % 96 + 1);
+        let mut prover = RepeatedProver::from_provers((0..k).map(|_| WrongClaim::new(honest.clone(), delta)).collect()).unwrap();
+        let claim = prover.claim();
+        let mut verifier = RepeatedVerifier::new((0..k).map(|_| sumcheck_ml::verifier::Verifier::with_options(g.clone(), claim, sumcheck_ml::verifier::VerifierOptions::default().allow_insecure()).unwrap()).collect()).unwrap();
+        assert!(!run(&mut prover, &mut verifier, &mut StdRng::seed_from_u64(seed)));
+    }
+
+    let g = parse_factors::<Fp97>("(24*x0 + 15*x0*x1 + 35*x1) * (17*x0 + 30*x0*x1 + 33*x1)").unwrap();
+    let bits = pml_soundness_bits::<Fp97>(&[2, 2]);
+    let mut prover = RepeatedProver::new(sumcheck_pml::prover::Prover::new(g.clone()).unwrap(), repetitions_for(config.min_bits, bits).unwrap()).unwrap();
+    let claim = prover.claim();
+    let mut verifier = RepeatedVerifier::for_security(&config, bits, || sumcheck_pml::verifier::Verifier::with_options(Product::new(g.clone()).unwrap(), claim, sumcheck_pml::verifier::VerifierOptions::default().allow_insecure()).unwrap()).unwrap();
+    assert!(run(&mut prover, &mut verifier, rng));
+
+    let g = parse_polynomial::<Fp97>("20*x0^2 + 5*x0^2*x1 + 29*x0*x1 + 62*x0^2*x1^2 + 90*x0*x1^2 + 88*x1^2").unwrap();
+    let bits = naive_soundness_bits::<Fp97>(&[2, 2]);
+    let mut prover = RepeatedProver::new(sumcheck_naive::Prover::new(g.clone()).unwrap(), repetitions_for(config.min_bits, bits).unwrap()).unwrap();
+    let claim = prover.claim();
+    let mut verifier = RepeatedVerifier::for_security(&config, bits, || {
+        let mut verifier = sumcheck_naive::Verifier::with_options(g.clone(), sumcheck_naive::VerifierOptions::default().allow_insecure()).unwrap();
+        verifier.set_c_1(claim);
+        verifier
+    }).unwrap();
+    assert!(run(&mut prover, &mut verifier, rng));
+}
+
+#[test]
+fn test_malformed_repetitions(){
+    let rng = &mut test_rng();
+    let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1 + 35*x1").unwrap();
+    let honest = sumcheck_ml::prover::Prover::new(g.clone()).unwrap();
+    let claim = honest.claim();
+    let verifier = || sumcheck_ml::verifier::Verifier::with_options(g.clone(), claim, sumcheck_ml::verifier::VerifierOptions::default().allow_insecure()).unwrap();
+
+    assert!(matches!(RepeatedProver::new(honest.clone(), 0), Err(Error::EmptyInput)));
+    assert!(matches!(RepeatedVerifier::new(Vec::<sumcheck_ml::verifier::Verifier<Fp97, Poly>>::new()), Err(Error::EmptyInput)));
+
+    let delta = Fp97::one();
+    let provers = vec![WrongClaim::new(honest.clone(), Fp97::from(0u8)), WrongClaim::new(honest.clone(), delta)];
+    assert_eq!(RepeatedProver::from_provers(provers).err(), Some(Error::RepetitionClaimMismatch(1, claim, claim + delta)));
+
+    let mut prover = RepeatedProver::new(honest, 2).unwrap();
+    assert_eq!(prover.round(&[Fp97::one()], 0).unwrap_err(), Error::WrongRepetitionCount(2, 1));
+    let mut messages = prover.round(&[Fp97::one(); 2], 0).unwrap();
+    messages.pop();
+    let mut verifier = RepeatedVerifier::new(vec![verifier(), verifier()]).unwrap();
+    assert_eq!(verifier.round(messages, rng).unwrap_err(), Error::WrongRepetitionCount(2, 1));
+}
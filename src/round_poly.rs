@@ -0,0 +1,278 @@
+//! The univariate polynomial a prover sends in every round, in either of the two forms the
+//! protocols use.
+//!
+//! [`crate::sumcheck_ml`] sends $(g_j(0), g_j(1))$, [`crate::sumcheck_pml`] the evaluations
+//! $g_j(0), ..., g_j(d)$ and [`crate::sumcheck_naive`] the coefficients of $g_j$. A
+//! [`RoundPolynomial`] holds either form and converts between them, so code handling round
+//! messages needs to be written once.
+use ark_ff::Field;
+use ark_poly::univariate;
+
+use crate::error::{check_evaluation_points, Error};
+
+/// A univariate round polynomial $g_j$ of degree $d$.
+#[derive(Debug, Clone)]
+pub struct RoundPolynomial<F: Field> {
+    form: Form<F>,
+}
+
+#[derive(Debug, Clone)]
+enum Form<F> {
+    /// $c_0, ..., c_d$ with $g_j = \sum_i c_i X^i$.
+    Coefficients(Vec<F>),
+
+    /// $g_j(0), ..., g_j(d)$, all distinct points of the field.
+    Evaluations(Vec<F>),
+}
+
+impl<F: Field> RoundPolynomial<F> {
+    /// The polynomial $\sum_i c_i X^i$.
+    pub fn from_coefficients(coeffs: Vec<F>) -> Self {
+        Self { form: Form::Coefficients(coeffs) }
+    }
+
+    /// The polynomial of degree $< evals.len()$ through $(t, evals[t])$, $t = 0,1,...$
+    ///
+    /// Fails if the characteristic of `F` is too small for the points to be distinct.
+    pub fn from_evaluations(evals: Vec<F>) -> Result<Self, Error<F>> {
+        check_evaluation_points(evals.len())?;
+        Ok(Self { form: Form::Evaluations(evals) })
+    }
+
+    /// Returns the coefficients $c_0, ..., c_d$, without trailing zeros.
+    pub fn to_coefficients(&self) -> Vec<F> {
+        let mut coeffs = match &self.form {
+            Form::Coefficients(coeffs) => coeffs.clone(),
+            Form::Evaluations(evals) => interpolate(evals),
+        };
+        while coeffs.last().is_some_and(|c| c.is_zero()) {
+            coeffs.pop();
+        }
+        coeffs
+    }
+
+    /// Returns the evaluations at $0, 1, ..., n-1$, with $n$ at least the number of values
+    /// stored in either form. Coefficient forms need $n > d$ for a lossless conversion.
+    pub fn to_evaluations(&self, n: usize) -> Vec<F> {
+        match &self.form {
+            Form::Evaluations(evals) if evals.len() >= n => evals[..n].to_vec(),
+            _ => (0..n).map(|t| self.evaluate(F::from(t as u64))).collect(),
+        }
+    }
+
+    /// Evaluates $g_j(x)$.
+    pub fn evaluate(&self, x: F) -> F {
+        match &self.form {
+            Form::Coefficients(coeffs) => coeffs.iter().rev().fold(F::zero(), |acc, c| acc * x + c),
+            Form::Evaluations(evals) => interpolate_at(evals, x),
+        }
+    }
+
+    /// Returns $g_j(0) + g_j(1)$, the value the verifier checks against the previous round.
+    pub fn sum_over_boolean(&self) -> F {
+        match &self.form {
+            Form::Evaluations(evals) if evals.len() >= 2 => evals[0] + evals[1],
+            _ => self.evaluate(F::zero()) + self.evaluate(F::one()),
+        }
+    }
+
+    /// Returns the degree of $g_j$, 0 for the zero polynomial.
+    pub fn degree(&self) -> usize {
+        self.to_coefficients().len().saturating_sub(1)
+    }
+}
+
+impl<F: Field> PartialEq for RoundPolynomial<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_coefficients() == other.to_coefficients()
+    }
+}
+
+impl<F: Field> Eq for RoundPolynomial<F> {}
+
+/// The linear round polynomial of [`crate::sumcheck_ml`] through $(0, p_0)$ and $(1, p_1)$.
+impl<F: Field> From<(F, F)> for RoundPolynomial<F> {
+    fn from((p0, p1): (F, F)) -> Self {
+        Self { form: Form::Evaluations(vec![p0, p1]) }
+    }
+}
+
+impl<F: Field> From<univariate::SparsePolynomial<F>> for RoundPolynomial<F> {
+    fn from(p: univariate::SparsePolynomial<F>) -> Self {
+        let mut coeffs = Vec::new();
+        for (i, c) in p.iter() {
+            if coeffs.len() <= *i {
+                coeffs.resize(*i + 1, F::zero());
+            }
+            coeffs[*i] += c;
+        }
+        Self::from_coefficients(coeffs)
+    }
+}
+
+impl<F: Field> From<RoundPolynomial<F>> for univariate::SparsePolynomial<F> {
+    fn from(p: RoundPolynomial<F>) -> Self {
+        univariate::SparsePolynomial::from_coefficients_vec(
+            p.to_coefficients().into_iter().enumerate().filter(|(_, c)| !c.is_zero()).collect(),
+        )
+    }
+}
+
+/// A round message of one of the protocols, viewed as a [`RoundPolynomial`].
+pub trait RoundMessage<F: Field>: Clone {
+    fn to_round_polynomial(&self) -> Result<RoundPolynomial<F>, Error<F>>;
+}
+
+impl<F: Field> RoundMessage<F> for (F, F) {
+    fn to_round_polynomial(&self) -> Result<RoundPolynomial<F>, Error<F>> {
+        Ok(RoundPolynomial::from(*self))
+    }
+}
+
+/// The evaluations $g_j(0), ..., g_j(d)$ sent by [`crate::sumcheck_pml`].
+impl<F: Field> RoundMessage<F> for Vec<F> {
+    fn to_round_polynomial(&self) -> Result<RoundPolynomial<F>, Error<F>> {
+        RoundPolynomial::from_evaluations(self.clone())
+    }
+}
+
+impl<F: Field> RoundMessage<F> for univariate::SparsePolynomial<F> {
+    fn to_round_polynomial(&self) -> Result<RoundPolynomial<F>, Error<F>> {
+        Ok(RoundPolynomial::from(self.clone()))
+    }
+}
+
+impl<F: Field> RoundMessage<F> for RoundPolynomial<F> {
+    fn to_round_polynomial(&self) -> Result<RoundPolynomial<F>, Error<F>> {
+        Ok(self.clone())
+    }
+}
+
+/// Evaluates polynomials of degree $< n$ given by their values at $0, 1, ..., n-1$.
+///
+/// The barycentric weights $w_i = 1 / \prod_{k \ne i} (i - k)$ only depend on $n$, so they
+/// are computed once, with a single inversion, and shared by every evaluation.
+pub(crate) struct Interpolator<F> {
+    weights: Vec<F>,
+}
+
+impl<F: Field> Interpolator<F> {
+    pub(crate) fn new(n: usize) -> Self {
+        // $w_i = (-1)^{n-1-i} / (i! (n-1-i)!)$ from the inverses of $0!, ..., (n-1)!$
+        let mut factorials = vec![F::one(); n];
+        for i in 1..n {
+            factorials[i] = factorials[i - 1] * F::from(i as u64);
+        }
+        let mut inverses = vec![F::one(); n];
+        let mut inverse = factorials.last().map_or(F::one(), |f| f.inverse().unwrap());
+        for i in (0..n).rev() {
+            inverses[i] = inverse;
+            inverse *= F::from(i as u64);
+        }
+        let weights = (0..n)
+            .map(|i| {
+                let w = inverses[i] * inverses[n - 1 - i];
+                if (n - 1 - i).is_multiple_of(2) { w } else { -w }
+            })
+            .collect();
+        Self { weights }
+    }
+
+    /// Evaluates at $x$ the polynomial through $(t, ys[t])$, `ys` holding $n$ values.
+    pub(crate) fn evaluate(&self, ys: &[F], x: F) -> F {
+        // $\sum_i w_i y_i \prod_{k \ne i} (x - k)$, the products split into the factors
+        // below and above $i$ so no inversion is needed
+        let mut below = Vec::with_capacity(ys.len());
+        let mut product = F::one();
+        for k in 0..ys.len() {
+            below.push(product);
+            product *= x - F::from(k as u64);
+        }
+        let mut above = F::one();
+        let mut result = F::zero();
+        for (i, (y, w)) in ys.iter().zip(&self.weights).enumerate().rev() {
+            result += *y * w * below[i] * above;
+            above *= x - F::from(i as u64);
+        }
+        result
+    }
+}
+
+/// Evaluates at $x$ the polynomial of degree $< ys.len()$ through $(t, ys[t])$, $t = 0,1,...$
+pub(crate) fn interpolate_at<F: Field>(ys: &[F], x: F) -> F {
+    Interpolator::new(ys.len()).evaluate(ys, x)
+}
+
+/// Returns the coefficients of the polynomial of degree $< ys.len()$ through $(t, ys[t])$.
+fn interpolate<F: Field>(ys: &[F]) -> Vec<F> {
+    let weights = Interpolator::<F>::new(ys.len()).weights;
+    let mut coeffs = vec![F::zero(); ys.len()];
+    for (i, (y, w)) in ys.iter().zip(weights).enumerate() {
+        // the Lagrange basis polynomial $w_i \prod_{k \ne i} (X - k)$
+        let mut basis = vec![F::one()];
+        for k in (0..ys.len()).filter(|k| *k != i) {
+            let k = F::from(k as u64);
+            basis.insert(0, F::zero());
+            for d in 0..basis.len() - 1 {
+                let next = basis[d + 1];
+                basis[d] -= k * next;
+            }
+        }
+
+        let scale = *y * w;
+        for (c, b) in coeffs.iter_mut().zip(basis) {
+            *c += scale * b;
+        }
+    }
+    coeffs
+}
+
+#[cfg(test)]
+mod test {
+    use ark_poly::Polynomial;
+
+    use super::*;
+    use crate::fields::Fp97;
+
+    fn fp(n: u64) -> Fp97 {
+        Fp97::from(n)
+    }
+
+    #[test]
+    fn test_conversions(){
+        // 3 + 2X + 5X^2
+        let coeffs = RoundPolynomial::from_coefficients(vec![fp(3), fp(2), fp(5)]);
+        let evals = RoundPolynomial::from_evaluations(vec![fp(3), fp(10), fp(27)]).unwrap();
+        assert_eq!(coeffs, evals);
+        assert_eq!(evals.to_coefficients(), vec![fp(3), fp(2), fp(5)]);
+        assert_eq!(coeffs.to_evaluations(3), vec![fp(3), fp(10), fp(27)]);
+        assert_eq!(coeffs.degree(), 2);
+        assert_eq!(evals.evaluate(fp(10)), fp(3 + 20 + 500));
+        assert_eq!(evals.sum_over_boolean(), fp(13));
+
+        // evaluations of a lower degree polynomial
+        let linear = RoundPolynomial::from_evaluations(vec![fp(1), fp(3), fp(5), fp(7)]).unwrap();
+        assert_eq!(linear.degree(), 1);
+        assert_eq!(linear, RoundPolynomial::from((fp(1), fp(3))));
+
+        let sparse = univariate::SparsePolynomial::from_coefficients_vec(vec![(0, fp(3)), (2, fp(5))]);
+        let round = RoundPolynomial::from(sparse.clone());
+        assert_eq!(round.evaluate(fp(4)), sparse.evaluate(&fp(4)));
+        assert_eq!(univariate::SparsePolynomial::from(round), sparse);
+
+        assert_eq!(RoundPolynomial::<Fp97>::from_coefficients(vec![]).degree(), 0);
+        assert!(matches!(RoundPolynomial::from_evaluations(vec![fp(0); 98]), Err(Error::FieldTooSmall(98))));
+    }
+
+    #[test]
+    fn test_interpolate_at(){
+        // 3 + 2X + 5X^2 + X^3 at the nodes, between them and beyond them
+        let p = |x: Fp97| fp(3) + fp(2) * x + fp(5) * x * x + x * x * x;
+        let ys: Vec<Fp97> = (0..4).map(|t| p(fp(t))).collect();
+        for x in [0, 2, 3, 7, 50, 96] {
+            assert_eq!(interpolate_at(&ys, fp(x)), p(fp(x)));
+        }
+        assert_eq!(interpolate_at(&[fp(5)], fp(11)), fp(5));
+        assert_eq!(interpolate_at::<Fp97>(&[], fp(11)), fp(0));
+    }
+}
@@ -0,0 +1,171 @@
+//! Sessions that run the provers and verifiers of every protocol in the right round order.
+//!
+//! [`SumcheckProver::round`] rejects a round index out of order but trusts the caller to
+//! pass the challenge of the previous round. A [`ProverSession`] instead yields the first message
+//! without a challenge and then takes exactly one challenge per message. Each step consumes
+//! the session and hands back the next state, so rounds cannot be skipped, repeated or run
+//! past the last one.
+use std::marker::PhantomData;
+
+use ark_ff::Field;
+use ark_std::rand::Rng;
+
+use crate::error::Error;
+use crate::protocol::{RoundOutcome, SumcheckProver, SumcheckVerifier};
+
+/// A prover that has sent the message of round `round` and waits for its challenge.
+pub struct ProverSession<F: Field, P: SumcheckProver<F>> {
+    prover: P,
+    round: usize,
+    _f: PhantomData<F>,
+}
+
+/// The state of a prover after sending a message.
+pub enum ProverStep<F: Field, P: SumcheckProver<F>> {
+    /// The prover sent `message` and waits for the verifier's challenge.
+    Round(ProverSession<F, P>, P::Message),
+
+    /// The prover sent its last `message`, nothing is left to do.
+    Done(P::Message),
+}
+
+impl<F: Field, P: SumcheckProver<F>> ProverSession<F, P> {
+    /// Starts the protocol, returning the claim and the first message.
+    pub fn start(mut prover: P) -> Result<(F, ProverStep<F, P>), Error<F>> {
+        let claim = prover.claim();
+        // the first round ignores the challenge
+        let message = prover.round(F::one(), 0)?;
+        Ok((claim, Self::step(prover, 0, message)))
+    }
+
+    /// Consumes the verifier's challenge for the current round and sends the next message.
+    pub fn challenge(mut self, r: F) -> Result<ProverStep<F, P>, Error<F>> {
+        let round = self.round + 1;
+        let message = self.prover.round(r, round)?;
+        Ok(Self::step(self.prover, round, message))
+    }
+
+    /// Returns the round whose message was sent last.
+    pub fn round(&self) -> usize {
+        self.round
+    }
+
+    fn step(prover: P, round: usize, message: P::Message) -> ProverStep<F, P> {
+        if round + 1 == prover.rounds() {
+            ProverStep::Done(message)
+        } else {
+            ProverStep::Round(Self { prover, round, _f: PhantomData }, message)
+        }
+    }
+}
+
+/// A verifier waiting for the message of round `round`.
+pub struct VerifierSession<F: Field, V: SumcheckVerifier<F>> {
+    verifier: V,
+    round: usize,
+    _f: PhantomData<F>,
+}
+
+/// The state of a verifier after checking a message.
+pub enum VerifierStep<F: Field, V: SumcheckVerifier<F>> {
+    /// The message was consistent, `F` is the challenge to send back.
+    Round(VerifierSession<F, V>, F),
+
+    /// The last message was checked against the oracle.
+    Verdict(bool),
+}
+
+impl<F: Field, V: SumcheckVerifier<F>> VerifierSession<F, V> {
+    /// Starts the protocol with a verifier that was built from the prover's claim.
+    pub fn start(verifier: V) -> Self {
+        Self { verifier, round: 0, _f: PhantomData }
+    }
+
+    /// Checks the prover's message for the current round, drawing the challenge from `rng`.
+    pub fn receive<R: Rng>(mut self, message: V::Message, rng: &mut R) -> Result<VerifierStep<F, V>, V::Error> {
+        Ok(match self.verifier.round(message, rng)? {
+            RoundOutcome::Challenge(r) => {
+                self.round += 1;
+                VerifierStep::Round(self, r)
+            }
+            RoundOutcome::Final(res) => VerifierStep::Verdict(res),
+        })
+    }
+
+    /// Returns the round whose message is expected next.
+    pub fn round(&self) -> usize {
+        self.round
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_std::test_rng;
+
+    use super::*;
+    use crate::fields::Fp97;
+    use crate::oracle::Product;
+    use crate::parser::{parse_factors, parse_polynomial};
+    use crate::{sumcheck_ml, sumcheck_naive, sumcheck_pml};
+
+    /// Runs both sessions to completion, returning the verdict and the number of rounds.
+    fn run<P, V>(prover: P, verifier: impl FnOnce(Fp97) -> V) -> Result<(bool, usize), Error<Fp97>>
+    where
+        P: SumcheckProver<Fp97>,
+        V: SumcheckVerifier<Fp97, Message = P::Message, Error = Error<Fp97>>,
+    {
+        let rng = &mut test_rng();
+        let (claim, mut step) = ProverSession::start(prover)?;
+        let mut verifier = VerifierSession::start(verifier(claim));
+        let mut rounds = 0;
+        loop {
+            rounds += 1;
+            match step {
+                ProverStep::Round(session, message) => {
+                    assert_eq!(session.round(), verifier.round());
+                    match verifier.receive(message, rng)? {
+                        VerifierStep::Round(next, r) => {
+                            verifier = next;
+                            step = session.challenge(r)?;
+                        }
+                        VerifierStep::Verdict(_) => panic!("verifier finished before the prover"),
+                    }
+                }
+                ProverStep::Done(message) => {
+                    return match verifier.receive(message, rng)? {
+                        VerifierStep::Verdict(res) => Ok((res, rounds)),
+                        VerifierStep::Round(..) => panic!("prover finished before the verifier"),
+                    };
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sessions(){
+        let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1 + 35*x1 + 3*x2").unwrap();
+        let prover = sumcheck_ml::prover::Prover::new(g.clone()).unwrap();
+        let verifier = |claim| sumcheck_ml::verifier::Verifier::with_options(g.clone(), claim, sumcheck_ml::verifier::VerifierOptions::default().allow_insecure()).unwrap();
+        assert_eq!(run(prover, verifier), Ok((true, 3)));
+
+        let g = parse_factors::<Fp97>("(24*x0 + 15*x0*x1 + 35*x1) * (17*x0 + 30*x0*x1 + 33*x1)").unwrap();
+        let prover = sumcheck_pml::prover::Prover::new(g.clone()).unwrap();
+        let verifier = |claim| sumcheck_pml::verifier::Verifier::with_options(Product::new(g.clone()).unwrap(), claim, sumcheck_pml::verifier::VerifierOptions::default().allow_insecure()).unwrap();
+        assert_eq!(run(prover, verifier), Ok((true, 2)));
+
+        let g = parse_polynomial::<Fp97>("20*x0^2 + 5*x0^2*x1 + 29*x0*x1").unwrap();
+        let prover = sumcheck_naive::Prover::new(g.clone()).unwrap();
+        let verifier = |claim| sumcheck_naive::Verifier::with_claim(g.clone(), claim, sumcheck_naive::VerifierOptions::default().allow_insecure()).unwrap();
+        assert_eq!(run(prover, verifier), Ok((true, 2)));
+
+        // a single round has no challenge for the prover at all
+        let g = parse_polynomial::<Fp97>("3*x0 + 5").unwrap();
+        let (_, step) = ProverSession::start(sumcheck_ml::prover::Prover::new(g).unwrap()).unwrap();
+        assert!(matches!(step, ProverStep::Done((p0, p1)) if p0 + p1 == Fp97::from(13u8)));
+
+        // and a partial prover is done after its own rounds
+        let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1 + 35*x1").unwrap();
+        let (_, step) = ProverSession::start(sumcheck_ml::prover::Prover::new(g).unwrap().partial(1).unwrap()).unwrap();
+        assert!(matches!(step, ProverStep::Done(_)));
+    }
+}
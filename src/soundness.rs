@@ -0,0 +1,142 @@
+//! Soundness error of the sumcheck protocols and the security level verifiers require.
+//!
+//! By the Schwartz-Zippel lemma a cheating prover survives round $j$ with probability at
+//! most $d_j/|F|$, where $d_j$ is the degree of the round polynomial, so the protocol has
+//! soundness error $\sum_j d_j / |F|$. It is reported in bits, $-\log_2$ of the error.
+use ark_ff::{Field, PrimeField};
+
+use crate::error::Error;
+
+/// The security level [`SecurityConfig::default`] requires.
+pub const DEFAULT_SECURITY_BITS: f64 = 128.0;
+
+/// Returns $\log_2 |F|$, counting the extension degree of `F`.
+pub fn log2_field_size<F: Field>() -> f64 {
+    let modulus = <F::BasePrimeField as PrimeField>::MODULUS;
+    let limbs = modulus.as_ref();
+    let top = limbs.iter().rposition(|l| *l != 0).unwrap_or(0);
+
+    // the two most significant limbs give far more precision than an f64 holds
+    let mut mantissa = limbs[top] as f64;
+    if top > 0 {
+        mantissa += limbs[top - 1] as f64 / 2f64.powi(64);
+    }
+    F::extension_degree() as f64 * (mantissa.log2() + 64.0 * top as f64)
+}
+
+/// Soundness in bits of $n$ rounds with round polynomials of degree at most $d$ over a
+/// field of $2^{log2\_field\_size}$ elements: $-\log_2(n d / |F|)$.
+pub fn soundness_bits(log2_field_size: f64, num_vars: usize, degree: usize) -> f64 {
+    log2_field_size - ((num_vars * degree) as f64).log2()
+}
+
+/// Soundness in bits of [`crate::sumcheck_ml`], whose round polynomials are linear.
+pub fn ml_soundness_bits<F: Field>(num_vars: usize) -> f64 {
+    soundness_bits(log2_field_size::<F>(), num_vars, 1)
+}
+
+/// Soundness in bits of [`crate::sumcheck_pml`], where `degrees[j]` is the degree of the
+/// product of the factors in its $j$-th variable, e.g. the number of factors for
+/// multilinear ones.
+pub fn pml_soundness_bits<F: Field>(degrees: &[usize]) -> f64 {
+    naive_soundness_bits::<F>(degrees)
+}
+
+/// Soundness in bits of [`crate::sumcheck_naive`], where `degrees[j]` is the degree of $g$
+/// in its $j$-th variable.
+pub fn naive_soundness_bits<F: Field>(degrees: &[usize]) -> f64 {
+    log2_field_size::<F>() - (degrees.iter().sum::<usize>() as f64).log2()
+}
+
+/// The minimum soundness a verifier accepts to run with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SecurityConfig {
+    /// Required soundness in bits.
+    pub min_bits: f64,
+
+    /// Run even when the soundness is below `min_bits`.
+    pub allow_insecure: bool,
+}
+
+impl Default for SecurityConfig {
+    fn default() -> Self {
+        Self::new(DEFAULT_SECURITY_BITS)
+    }
+}
+
+impl SecurityConfig {
+    pub fn new(min_bits: f64) -> Self {
+        Self {
+            min_bits,
+            allow_insecure: false,
+        }
+    }
+
+    /// Explicitly opts in to running below the configured security level, e.g. for tests
+    /// over toy fields.
+    pub fn allow_insecure(mut self) -> Self {
+        self.allow_insecure = true;
+        self
+    }
+
+    /// Returns whether a protocol with `bits` of soundness may run.
+    pub fn permits(&self, bits: f64) -> bool {
+        self.allow_insecure || bits >= self.min_bits
+    }
+
+    /// Fails with [`Error::InsufficientSecurity`] unless a protocol with `bits` of soundness
+    /// may run.
+    pub fn check<F: Field>(&self, bits: f64) -> Result<(), Error<F>> {
+        if !self.permits(bits) {
+            return Err(Error::InsufficientSecurity(bits, self.min_bits));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fields::Fp97;
+    use crate::parser::parse_polynomial;
+    use crate::sumcheck_ml::verifier::VerifierOptions;
+    use crate::{sumcheck_ml, sumcheck_naive};
+
+    #[test]
+    fn test_soundness_bits(){
+        assert!((log2_field_size::<Fp97>() - 97f64.log2()).abs() < 1e-9);
+
+        assert!((ml_soundness_bits::<Fp97>(2) - (97f64.log2() - 1.0)).abs() < 1e-9);
+        assert!((naive_soundness_bits::<Fp97>(&[2, 2]) - (97f64.log2() - 2.0)).abs() < 1e-9);
+
+        // a 97-element field is never secure unless explicitly overridden
+        let config = SecurityConfig::new(40.0);
+        let claim = Fp97::from(1u8);
+        let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1 + 35*x1").unwrap();
+        assert!(matches!(sumcheck_ml::verifier::Verifier::new(g.clone(), claim), Err(Error::InsufficientSecurity(_, min)) if min == DEFAULT_SECURITY_BITS));
+        assert!(sumcheck_ml::verifier::Verifier::with_options(g.clone(), claim, VerifierOptions::default().security(config)).is_err());
+        assert!(sumcheck_ml::verifier::Verifier::with_options(g.clone(), claim, VerifierOptions::default().security(config).allow_insecure()).is_ok());
+        assert!(sumcheck_naive::Verifier::new(g.clone()).is_err());
+        assert!(sumcheck_naive::Verifier::with_options(g.clone(), sumcheck_naive::VerifierOptions::default().security(config)).is_err());
+        assert!(sumcheck_naive::Verifier::with_options(g, sumcheck_naive::VerifierOptions::default().allow_insecure()).is_ok());
+    }
+
+    #[cfg(feature = "goldilocks")]
+    #[test]
+    fn test_goldilocks_soundness(){
+        use crate::fields::Goldilocks;
+        use crate::oracle::Product;
+        use crate::parser::parse_factors;
+        use crate::sumcheck_pml;
+
+        assert!((log2_field_size::<Goldilocks>() - 64.0).abs() < 1e-6);
+        assert!((pml_soundness_bits::<Goldilocks>(&[4; 16]) - 58.0).abs() < 1e-6);
+        assert!((pml_soundness_bits::<Goldilocks>(&[3, 1]) - 62.0).abs() < 1e-6);
+
+        let config = SecurityConfig::new(40.0);
+        let g = parse_factors::<Goldilocks>("(24*x0 + 15*x0*x1 + 35*x1) * (17*x0 + 30*x0*x1 + 33*x1)").unwrap();
+        let options = sumcheck_pml::verifier::VerifierOptions::default();
+        assert!(sumcheck_pml::verifier::Verifier::with_options(Product::new(g.clone()).unwrap(), Goldilocks::from(1u8), options.security(config)).is_ok());
+        assert!(sumcheck_pml::verifier::Verifier::new(Product::new(g).unwrap(), Goldilocks::from(1u8)).is_err());
+    }
+}
@@ -1,6 +1,32 @@
 pub mod prover;
 pub mod verifier;
-pub mod poly;
 
 #[cfg(test)]
-mod test;
\ No newline at end of file
+mod test;
+
+use ark_ff::Field;
+
+use crate::error::Error;
+use crate::fiat_shamir::{check_oracle, verify_batch_with, verify_rounds, BatchReport, Proof, SubClaim};
+use crate::oracle::EvaluationOracle;
+use crate::soundness::SecurityConfig;
+use crate::sumcheck_ml::verifier::{oracle_degree_bounds, VerifierOptions};
+
+/// Checks a whole [`Proof`] that $g$ sums to `claim`, as made by
+/// [`crate::fiat_shamir::prove`] from a [`prover::Prover`] binding in the default order,
+/// and its last round against $g$. $g$ has to be multilinear. Fails with
+/// [`Error::InsufficientSecurity`] before the first round unless the protocol reaches the
+/// soundness `security` requires.
+pub fn verify<F: Field, O: EvaluationOracle<F>>(proof: &Proof<(F, F)>, claim: F, g: &mut O, security: SecurityConfig) -> Result<SubClaim<F>, Error<F>> {
+    let subclaim = verify_rounds(proof, claim, &oracle_degree_bounds(g, &VerifierOptions::default().security(security))?)?;
+    let value = g.query(&subclaim.point)?;
+    check_oracle(subclaim, value)
+}
+
+/// Same as [`verify`] for many proofs about $g$ with their claims. The rounds of every
+/// proof are checked on their own, only the final queries are shared: $g$ answers all of
+/// them in one [`EvaluationOracle::query_many`].
+pub fn verify_batch<F: Field, O: EvaluationOracle<F>>(proofs: &[(Proof<(F, F)>, F)], g: &mut O, security: SecurityConfig) -> Result<BatchReport<F>, Error<F>> {
+    let degrees = oracle_degree_bounds(g, &VerifierOptions::default().security(security))?;
+    verify_batch_with(proofs, &degrees, |points| g.query_many(points))
+}
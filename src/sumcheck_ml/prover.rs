@@ -1,57 +1,171 @@
+use std::sync::Arc;
+
 use ark_ff::Field;
-use crate::sumcheck_ml::poly::*;
-/// The state of the Prover.
-pub struct Prover<F: Field, P: SumCheckPolynomial<F>> {
-    g: P,   // $g$ a polynomial being used in this run of the protocol.
+use crate::error::{check_prover_round, Error};
+use crate::poly::{BindingOrder, HypercubeEvaluation};
+/// The state of the Prover. The polynomial $g$ is known only through `table`, so the
+/// prover does not depend on its type.
+#[derive(Clone)]
+pub struct Prover<F: Field> {
     claim: F, // $claim$ a value prover _claims_ equal the true answer.
     r: Vec<F>,// Random values $r_1,...,r_j$ sent by the [`Verifier`] in the previous rounds.
     num_vars: usize,
-    table:Vec<F>
+    rounds: usize,// Number of rounds to run, less than `num_vars` in a partial run.
+    table: Arc<Vec<F>>,// Shared by clones of the prover until they fold it.
+    order: BindingOrder,
+}
+
+/// What is left of a partial run: $g$ with the variables bound so far fixed to the
+/// challenges, as its table over the remaining variables, and the sum that table has.
+///
+/// The remaining variables keep their relative order. A later stage continues with
+/// [`Prover::resume`], or with any prover for the polynomial the table describes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Residual<F: Field> {
+    pub table: Vec<F>,
+    pub claim: F,
+    /// The challenges of the rounds played, in the order of the rounds.
+    pub challenges: Vec<F>,
 }
 
-impl<F: Field, P: SumCheckPolynomial<F>> Prover<F, P> {
-    /// Create a new [`Prover`] state with the polynomial $g$.
-    pub fn new(g: P) -> Self {
+impl<F: Field> Prover<F> {
+    /// Create a new [`Prover`] state with the polynomial $g$, which must have at least
+    /// one variable. Only the values of $g$ on the hypercube are used, so for a $g$ that is
+    /// not multilinear the verifier has to run in
+    /// [`crate::sumcheck_ml::verifier::OracleMode::MultilinearExtension`].
+    pub fn new<P: HypercubeEvaluation<F>>(g: P) -> Result<Self, Error<F>> {
+        Self::with_binding_order(g, BindingOrder::default())
+    }
+
+    /// Same as [`Prover::new`], but binds the variables in the given order.
+    pub fn with_binding_order<P: HypercubeEvaluation<F>>(g: P, order: BindingOrder) -> Result<Self, Error<F>> {
         let num_vars = g.num_vars();
+        if num_vars == 0 {
+            return Err(Error::EmptyInput);
+        }
         let table = g.to_evaluations();
         let claim = table.iter().sum();
+        let table = Arc::new(table);
 
-        Self {
-            g,
+        Ok(Self {
             claim,
             num_vars,
+            rounds: num_vars,
             r: Vec::with_capacity(num_vars),
-            table
-        }
+            table,
+            order,
+        })
     }
 
     /// Get the value $claim$ that prover claims equal true answer.
     pub fn claim(&self) -> F { self.claim }
 
-    /// Perform $j$-th round of the [`Prover`] side of the prococol.
-    pub fn round(&mut self, r_prev: F, j: usize) -> (F,F) {
-        
+    /// Fails with [`Error::FalseClaim`] unless `claim`, the public input, is the sum of the
+    /// table.
+    pub fn for_claim(self, claim: F) -> Result<Self, Error<F>> {
+        if claim != self.claim {
+            return Err(Error::FalseClaim(claim, self.claim));
+        }
+        Ok(self)
+    }
+
+    /// Runs only the first `rounds` rounds, after which [`Prover::into_residual`] hands the
+    /// claim about the remaining variables to the next stage.
+    pub fn partial(mut self, rounds: usize) -> Result<Self, Error<F>> {
+        if rounds == 0 || rounds > self.num_vars {
+            return Err(Error::InvalidRoundCount(rounds, self.num_vars));
+        }
+        self.rounds = rounds;
+        Ok(self)
+    }
+
+    /// Continues from the [`Residual`] of a partial run, binding its variables low to high.
+    /// Fails unless its table has at least two entries and sums to its claim.
+    pub fn resume(residual: Residual<F>) -> Result<Self, Error<F>> {
+        let Residual { table, claim, .. } = residual;
+        if table.len() < 2 {
+            return Err(Error::EmptyInput);
+        }
+        if !table.len().is_power_of_two() {
+            return Err(Error::WrongTableLength(table.len().next_power_of_two(), table.len()));
+        }
+        let num_vars = table.len().trailing_zeros() as usize;
+        let sum = table.iter().sum();
+        if sum != claim {
+            return Err(Error::FalseClaim(claim, sum));
+        }
+        Ok(Self {
+            claim,
+            num_vars,
+            rounds: num_vars,
+            r: Vec::with_capacity(num_vars),
+            table: Arc::new(table),
+            order: BindingOrder::default(),
+        })
+    }
+
+    /// Binds the variable of the last round played to its challenge `r_last` and returns
+    /// what is left for the remaining variables. Fails with [`Error::InvalidRoundCount`]
+    /// unless the rounds played are exactly [`Prover::num_rounds`].
+    pub fn into_residual(mut self, r_last: F) -> Result<Residual<F>, Error<F>> {
+        if self.r.len() + 1 != self.rounds {
+            return Err(Error::InvalidRoundCount(self.r.len() + 1, self.rounds));
+        }
+        self.fold(r_last);
+        self.r.push(r_last);
+        let table = Arc::try_unwrap(self.table).unwrap_or_else(|table| table.to_vec());
+        Ok(Residual { claim: table.iter().sum(), table, challenges: self.r })
+    }
+
+    /// Binds the next variable to `r`, halving the table. A table still shared with clones
+    /// of the prover, e.g. other repetitions, is left to them and the halves written anew.
+    fn fold(&mut self, r: F) {
+        let half = self.table.len() / 2;
+        let order = self.order;
+        let fold = |table: &[F], b: usize| {
+            let (lo, hi) = order.pair(b, half);
+            table[lo] + (table[hi] - table[lo]) * r
+        };
+        match Arc::get_mut(&mut self.table) {
+            Some(table) => {
+                for b in 0..half {
+                    table[b] = fold(table, b);
+                }
+                table.truncate(half);
+            }
+            None => self.table = Arc::new((0..half).map(|b| fold(&self.table, b)).collect()),
+        }
+    }
+
+    /// Perform $j$-th round of the [`Prover`] side of the prococol. Fails unless $j$ is the
+    /// next round and within [`Prover::num_rounds`].
+    pub fn round(&mut self, r_prev: F, j: usize) -> Result<(F,F), Error<F>> {
+        check_prover_round(j, &self.r, self.rounds)?;
         if j != 0 {
             self.r.push(r_prev);
             // g.fix_variables(&[r_prev])
-            for b in 0..2usize.pow((self.num_vars - j)as u32){
-                self.table[b] = self.table[b<<1] * (F::one() - r_prev) + self.table[(b << 1) + 1] * r_prev;
-            }
-
+            self.fold(r_prev);
         }
 
         let mut p0 = F::zero();
         let mut p1 = F::zero();
         // evaluating points - p0,p1
-        for b in 0..2usize.pow((self.num_vars - j - 1)as u32){
-            p0 += self.table[b<<1];
-            p1 += self.table[(b << 1) + 1];
+        let half = 2usize.pow((self.num_vars - j - 1)as u32);
+        for b in 0..half{
+            let (lo, hi) = self.order.pair(b, half);
+            p0 += self.table[lo];
+            p1 += self.table[hi];
         }
-        
-        (p0,p1)
+
+        Ok((p0,p1))
     }
 
     pub fn num_vars(&self) -> usize {
         self.num_vars
     }
+
+    /// Returns the number of rounds to run, all of them unless the run is partial.
+    pub fn num_rounds(&self) -> usize {
+        self.rounds
+    }
 }
@@ -1,66 +1,192 @@
-use ark_ff::{fields::Fp64,fields::{MontBackend, MontConfig},One, PrimeField,};
-use ark_poly::{multivariate::{self, Term, SparseTerm},DenseMVPolynomial, };
+use ark_ff::{Field, One, UniformRand};
+use ark_poly::{multivariate::{self, SparseTerm},DenseMVPolynomial, };
 use ark_std::test_rng;
 
+use crate::fields::Fp97;
+use crate::parser::parse_polynomial;
+use crate::poly::{BindingOrder, Mle, OracleEvaluation};
+use crate::protocol::run_protocol;
 use crate::sumcheck_ml::prover::*;
 use crate::sumcheck_ml::verifier::*;
 
 
+fn normal_poly<F: Field>(){
+	let rng = &mut test_rng();
+        
+    // 24 * x_0   +   15 * x_0 * x_1   +   35 * x_1
+    let g: multivariate::SparsePolynomial<F, SparseTerm> = parse_polynomial("24*x0 + 15*x0*x1 + 35*x1").unwrap();
+
+    let prover = Prover::new(g.clone()).unwrap();
+    let outcome = run_protocol(prover, |claim| Verifier::with_options(g.clone(), claim, VerifierOptions::default().allow_insecure()).unwrap(), rng).unwrap();
+
+    assert!(outcome.accepted);
+    assert_eq!(outcome.transcript.rounds.len(), DenseMVPolynomial::num_vars(&g));
+}
+
 #[test]
 fn test_normal_poly(){
-    #[derive(MontConfig)]
-    #[modulus = "97"]
-    #[generator = "5"]
-    struct FrConfig;
+    for_each_field!(F => normal_poly::<F>());
+}
 
-    type Fp97 = Fp64<MontBackend<FrConfig, 1>>;
+/// Runs the rounds with the given challenges, returning the value the last round
+/// polynomial takes at the last challenge, the prover's subclaim.
+fn subclaim(g: &multivariate::SparsePolynomial<Fp97, SparseTerm>, order: BindingOrder, r: &[Fp97]) -> Fp97 {
+    let mut prover = Prover::with_binding_order(g.clone(), order).unwrap();
+    let mut r_prev = Fp97::one();
+    let mut value = prover.claim();
+    for (j, r_j) in r.iter().enumerate() {
+        let (p0, p1) = prover.round(r_prev, j).unwrap();
+        assert_eq!(p0 + p1, value);
+        value = p0 + *r_j * (p1 - p0);
+        r_prev = *r_j;
+    }
+    value
+}
 
+#[test]
+fn test_binding_order(){
+    let rng = &mut test_rng();
+    let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1 + 35*x1*x2 + 3*x2").unwrap();
 
-	let rng = &mut test_rng();
-        
-    // 24 * x_0   +   15 * x_0 * x_1   +   35 * x_1
-    let g: multivariate::SparsePolynomial<_, SparseTerm> = multivariate::SparsePolynomial::from_coefficients_slice(
-		2,
-		&[
-			(
-				Fp97::from_bigint(24u32.into()).unwrap(),
-				multivariate::SparseTerm::new(vec![(0, 1)]),
-			),
-			(
-				Fp97::from_bigint(15u32.into()).unwrap(),
-				multivariate::SparseTerm::new(vec![(0, 1), (1, 1)]),
-			),
-			(
-				Fp97::from_bigint(35u32.into()).unwrap(),
-				multivariate::SparseTerm::new(vec![(1, 1)]),
-			),
-		],
-	);
-	println!("=============== Given Polynomial ===================");
-	println!("{:?}",g.clone());
-	println!("==========================================================");
-
-    let mut prover = Prover::new(g.clone());
-    let mut verifier = Verifier::new(Some(g.clone()),prover.claim());
-
-	println!("prover.claim => {:?}",prover.claim());
-
-    let mut r_j = Fp97::one();
-
-    for j in 0..DenseMVPolynomial::num_vars(&g) {
-		let (p0,p1) = prover.round(r_j, j);
-		println!("Round-{} p => {:?} , {:?}",j,&p0,&p1);
-		let verifier_res = verifier.round(p0,p1, rng).unwrap();
-		match verifier_res {
-			VerifierRoundResult::JthRound(r) => {
-                println!("Round-{} V's r => {:?} expect => {:?}",j,&r,verifier.expect);
-				r_j = r;
-			}
-			VerifierRoundResult::FinalRound(res) => {
-				assert!(res);
-				break;
-			}
-		}
-	}
-
-}
\ No newline at end of file
+    for order in [BindingOrder::LowToHigh, BindingOrder::HighToLow] {
+        let prover = Prover::with_binding_order(g.clone(), order).unwrap();
+        let verifier = |claim| Verifier::with_options(g.clone(), claim, VerifierOptions::default().allow_insecure().binding_order(order)).unwrap();
+        assert!(run_protocol(prover, verifier, rng).unwrap().accepted);
+    }
+
+    // both orders reduce the claim to the value of $g$ at the point the challenges bind
+    let r: Vec<Fp97> = (0..3).map(|_| Fp97::rand(rng)).collect();
+    let expected = OracleEvaluation::evaluate(&g, &r).unwrap();
+    assert_eq!(subclaim(&g, BindingOrder::LowToHigh, &r), expected);
+    let reversed: Vec<Fp97> = r.iter().rev().copied().collect();
+    assert_eq!(subclaim(&g, BindingOrder::HighToLow, &reversed), expected);
+
+    // a verifier expecting the other order rejects
+    let prover = Prover::with_binding_order(g.clone(), BindingOrder::HighToLow).unwrap();
+    assert!(!run_protocol(prover, |claim| Verifier::with_options(g.clone(), claim, VerifierOptions::default().allow_insecure()).unwrap(), rng).unwrap().accepted);
+}
+
+#[test]
+fn test_round_out_of_order(){
+    use crate::error::Error;
+
+    let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1 + 35*x1").unwrap();
+    let mut prover = Prover::new(g).unwrap();
+    assert_eq!(prover.clone().round(Fp97::one(), 2).unwrap_err(), Error::RoundOutOfOrder(2, 2));
+    prover.round(Fp97::one(), 0).unwrap();
+    // the first round may be asked for again, as it takes no challenge
+    prover.round(Fp97::one(), 0).unwrap();
+    prover.round(Fp97::one(), 1).unwrap();
+    assert_eq!(prover.round(Fp97::one(), 1).unwrap_err(), Error::RoundOutOfOrder(1, 2));
+    assert_eq!(prover.round(Fp97::one(), 2).unwrap_err(), Error::RoundOutOfOrder(2, 2));
+}
+
+#[test]
+fn test_non_multilinear(){
+    use crate::error::Error;
+
+    let rng = &mut test_rng();
+    // degree 2 in $x_1$, its multilinear extension replaces $x_1^2$ by $x_1$
+    let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1^2 + 35*x1").unwrap();
+    assert!(matches!(Verifier::new(g.clone(), Fp97::one()), Err(Error::NotMultilinear(1, 2))));
+
+    let prover = Prover::new(g.clone()).unwrap();
+    let verifier = |claim| Verifier::with_options(g.clone(), claim, VerifierOptions::default().allow_insecure().oracle_mode(OracleMode::MultilinearExtension)).unwrap();
+    assert!(run_protocol(prover, verifier, rng).unwrap().accepted);
+
+    // and combines with the other binding order
+    let options = VerifierOptions::default().allow_insecure().oracle_mode(OracleMode::MultilinearExtension).binding_order(BindingOrder::HighToLow);
+    let prover = Prover::with_binding_order(g.clone(), BindingOrder::HighToLow).unwrap();
+    assert!(run_protocol(prover, |claim| Verifier::with_options(g.clone(), claim, options).unwrap(), rng).unwrap().accepted);
+
+    // the mode works for multilinear input as well
+    let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1 + 35*x1").unwrap();
+    let prover = Prover::new(g.clone()).unwrap();
+    let verifier = |claim| Verifier::with_options(g.clone(), claim, VerifierOptions::default().allow_insecure().oracle_mode(OracleMode::MultilinearExtension)).unwrap();
+    assert!(run_protocol(prover, verifier, rng).unwrap().accepted);
+}
+
+#[test]
+fn test_public_claim(){
+    use crate::error::Error;
+    use crate::protocol::run_protocol_on_claim;
+
+    let rng = &mut test_rng();
+    let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1 + 35*x1").unwrap();
+    // 24 + 35 + (24 + 15 + 35) over the four points
+    let public = Fp97::from(133u8);
+
+    let prover = Prover::new(g.clone()).unwrap().for_claim(public).unwrap();
+    let verifier = |claim| Verifier::with_options(g.clone(), claim, VerifierOptions::default().allow_insecure()).unwrap();
+    let outcome = run_protocol_on_claim(prover, public, verifier, rng).unwrap();
+    assert!(outcome.accepted);
+    assert_eq!(outcome.transcript.claim, public);
+
+    // the prover refuses a false statement up front
+    let false_claim = public + Fp97::one();
+    assert!(matches!(Prover::new(g.clone()).unwrap().for_claim(false_claim), Err(Error::FalseClaim(c, s)) if c == false_claim && s == public));
+
+    // a verifier holding a claim from elsewhere rejects the honest prover's first message
+    let res = run_protocol_on_claim(Prover::new(g.clone()).unwrap(), false_claim, verifier, rng);
+    assert_eq!(res.unwrap_err(), Error::ClaimMismatch(0, false_claim, public));
+}
+
+#[test]
+fn test_partial(){
+    use crate::error::Error;
+
+    let rng = &mut test_rng();
+    let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1 + 35*x1*x2 + 3*x2*x3 + x0*x3").unwrap();
+    let y = [Fp97::from(7u8), Fp97::from(11u8)];
+
+    for order in [BindingOrder::LowToHigh, BindingOrder::HighToLow] {
+        let mut prover = Prover::with_binding_order(g.clone(), order).unwrap().partial(2).unwrap();
+        let mut verifier = Verifier::with_options(g.clone(), prover.claim(), VerifierOptions::default().allow_insecure().binding_order(order)).unwrap().partial(2).unwrap();
+        let mut r = Fp97::one();
+        for j in 0..prover.num_rounds() {
+            let (p0, p1) = prover.round(r, j).unwrap();
+            match verifier.round(p0, p1, rng).unwrap() {
+                VerifierRoundResult::JthRound(r_j) => r = r_j,
+                VerifierRoundResult::FinalRound(_) => unreachable!(),
+            }
+        }
+        assert_eq!(prover.clone().round(r, 2).unwrap_err(), Error::RoundOutOfOrder(2, 2));
+        assert_eq!(verifier.round(r, r, rng).unwrap_err(), Error::RoundOutOfOrder(2, 2));
+
+        let residual = prover.into_residual(r).unwrap();
+        assert_eq!(residual.claim, verifier.expect);
+        assert_eq!(residual.challenges, verifier.challenges());
+
+        // the residual table is $g$ with the bound variables fixed
+        let bound = order.point(&residual.challenges);
+        let point = match order {
+            BindingOrder::LowToHigh => [bound, y.to_vec()].concat(),
+            BindingOrder::HighToLow => [y.to_vec(), bound].concat(),
+        };
+        let rest = Mle::from_evaluations(2, residual.table.clone()).unwrap();
+        assert_eq!(rest.evaluate(&y), g.evaluate(&point));
+
+        // the next stage proves the residual claim about the rest
+        let claim = residual.claim;
+        let prover = Prover::resume(residual).unwrap();
+        assert!(run_protocol(prover, |_| Verifier::with_options(rest.clone(), claim, VerifierOptions::default().allow_insecure()).unwrap(), rng).unwrap().accepted);
+    }
+
+    // a partial run goes through run_protocol like a full one
+    let mut prover = Prover::new(g.clone()).unwrap().partial(2).unwrap();
+    let verifier = |claim| Verifier::with_options(g.clone(), claim, VerifierOptions::default().allow_insecure()).unwrap().partial(2).unwrap();
+    let outcome = run_protocol(&mut prover, verifier, rng).unwrap();
+    assert!(outcome.accepted);
+    let r_last = outcome.transcript.challenges[1];
+    let residual = prover.into_residual(r_last).unwrap();
+    assert_eq!(residual.claim, outcome.transcript.rounds[1].evaluate(r_last));
+    assert_eq!(residual.challenges, outcome.transcript.challenges);
+
+    let prover = Prover::new(g.clone()).unwrap();
+    assert!(matches!(prover.clone().partial(0), Err(Error::InvalidRoundCount(0, 4))));
+    assert!(matches!(prover.clone().partial(5), Err(Error::InvalidRoundCount(5, 4))));
+    // stopping before the partial run is over
+    let mut prover = prover.partial(2).unwrap();
+    prover.round(Fp97::one(), 0).unwrap();
+    assert_eq!(prover.into_residual(Fp97::one()).unwrap_err(), Error::InvalidRoundCount(1, 2));
+}
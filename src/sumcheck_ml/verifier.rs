@@ -1,29 +1,37 @@
 use ark_ff::Field;
-use crate::sumcheck_ml::poly::*;
-use ark_std::{rand::Rng};
-
-pub trait RngF<F> {
-    fn draw(&mut self) -> F;
-}
-
-impl<F: Field, T: Rng> RngF<F> for T {
-    fn draw(&mut self) -> F {
-		let t = F::rand(self);
-		// println!("RngF::draw()# {:?} {:?}",&t,&t.to_string());
-        t
-    }
-}
-
+use crate::error::Error;
+use crate::oracle::EvaluationOracle;
+use crate::poly::{evaluate_table, BindingOrder, BooleanHypercube, RngF};
+use crate::round_poly::RoundPolynomial;
+use crate::soundness::{ml_soundness_bits, SecurityConfig};
 
 /// The state of the Verifier.
-pub struct Verifier<F: Field, P: SumCheckPolynomial<F>> {
+pub struct Verifier<F: Field, O: EvaluationOracle<F>> {
     n: usize,// Number of variables in the original polynomial.
-    claim: F,// A $claim$ value claimed by the Prover.
+    rounds: usize,// Number of rounds to run, less than `n` in a partial run.
     r: Vec<F>,// Previously picked random values $r_1,...,r_{j-1}$.
-    g: Option<P>,// Original polynomial for oracle access
+    g: O,// Oracle access to the original polynomial
+    order: BindingOrder,// Order the prover binds the variables in.
+    mode: OracleMode,// What the last round is checked against.
     pub expect:F
 }
 
+/// What the [`Verifier`] compares the last round against.
+///
+/// The [`crate::sumcheck_ml::prover::Prover`] only sees the values of $g$ on the hypercube,
+/// so it proves the sum of their multilinear extension $\tilde g$, which agrees with $g$
+/// only if $g$ is multilinear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OracleMode {
+    /// $g$ itself. Polynomials of degree more than 1 in any variable are rejected up front.
+    #[default]
+    Polynomial,
+
+    /// $\tilde g$, computed from the $2^n$ hypercube values the oracle gives for $g$ in the
+    /// last round. Works for any $g$, at the cost of a linear-time verifier.
+    MultilinearExtension,
+}
+
 /// Values returned by Validator as a result of its run on every step.
 #[derive(Debug)]
 pub enum VerifierRoundResult<F: Field> {
@@ -31,19 +39,94 @@ pub enum VerifierRoundResult<F: Field> {
     FinalRound(bool),
 }
 
-impl<F: Field, P: SumCheckPolynomial<F>> Verifier<F, P> {
-    /// Create the new state of the [`Verifier`].
-    /// $n$ - degree of the polynomial
+/// How a [`Verifier`] runs, see [`Verifier::with_options`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct VerifierOptions {
+    /// Order the prover binds the variables in.
+    pub order: BindingOrder,
+
+    /// What the last round is checked against.
+    pub mode: OracleMode,
+
+    /// Soundness the verifier refuses to run below.
+    pub security: SecurityConfig,
+}
+
+impl VerifierOptions {
+    /// For a prover binding the variables in the given order.
+    pub fn binding_order(mut self, order: BindingOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Checks the last round against `mode`.
+    pub fn oracle_mode(mut self, mode: OracleMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Refuses to run if the protocol does not reach the soundness required by `config`
+    /// instead of [`SecurityConfig::default`].
+    pub fn security(mut self, config: SecurityConfig) -> Self {
+        self.security = config;
+        self
+    }
+
+    /// Runs even below the required soundness, see [`SecurityConfig::allow_insecure`].
+    pub fn allow_insecure(mut self) -> Self {
+        self.security = self.security.allow_insecure();
+        self
+    }
+}
+
+impl<F: Field, O: EvaluationOracle<F>> Verifier<F, O> {
+    /// Create the new state of the [`Verifier`], which refuses to run below the soundness
+    /// [`SecurityConfig::default`] requires.
     /// $claim$ - the value claimed to be true answer by the [`Prover`].
-    pub fn new(g: Option<P>,claim: F) -> Self {
-		let num_vars = g.as_ref().unwrap().num_vars();
-        Self {
+    /// $g$ - oracle access to the polynomial for the [`Verifier`], e.g. the polynomial itself.
+    pub fn new(g: O,claim: F) -> Result<Self, Error<F>> {
+        Self::with_options(g, claim, VerifierOptions::default())
+    }
+
+    /// Same as [`Verifier::new`], running as `options` say.
+    pub fn with_options(g: O, claim: F, options: VerifierOptions) -> Result<Self, Error<F>> {
+		let num_vars = oracle_degree_bounds(&g, &options)?.len();
+        Ok(Self {
             n:num_vars,
-            claim,
+            rounds: num_vars,
             r: Vec::with_capacity(num_vars),
             g,
+            order: options.order,
+            mode: options.mode,
             expect:claim
+        })
+    }
+
+    /// Returns the number of variables, which is also the number of rounds.
+    pub fn num_vars(&self) -> usize {
+        self.n
+    }
+
+    /// Runs only the first `rounds` rounds. The last of them returns a challenge instead of
+    /// checking $g$, and leaves [`Verifier::expect`] as the claimed sum of $g$ over the
+    /// remaining variables with the bound ones fixed to [`Verifier::challenges`]. The next
+    /// stage checks that claim, e.g. with a new [`Verifier`] for that polynomial.
+    pub fn partial(mut self, rounds: usize) -> Result<Self, Error<F>> {
+        if rounds == 0 || rounds > self.n {
+            return Err(Error::InvalidRoundCount(rounds, self.n));
         }
+        self.rounds = rounds;
+        Ok(self)
+    }
+
+    /// Returns the oracle, e.g. to read what a [`crate::oracle::CountingOracle`] counted.
+    pub fn oracle(&self) -> &O {
+        &self.g
+    }
+
+    /// Returns the challenges drawn so far, in the order of the rounds.
+    pub fn challenges(&self) -> &[F] {
+        &self.r
     }
 
     /// Perform the $j$-th round of the [`Verifier`] side of the protocol.
@@ -51,26 +134,51 @@ impl<F: Field, P: SumCheckPolynomial<F>> Verifier<F, P> {
         &mut self,
         p0:F,p1:F,
         rng: &mut R,
-    ) -> Result<VerifierRoundResult<F>, Error> {
+    ) -> Result<VerifierRoundResult<F>, Error<F>> {
 		let r_j = rng.draw();
 
-        if self.expect != p0 + p1 {
-            Err(Error::ProverClaimMismatch(
-                format!("{:?}", self.expect),
-                format!("{:?} {:?}", p0 , p1),
-            ))
+        let j = self.r.len();
+        let g_j = RoundPolynomial::from((p0, p1));
+        if j == self.rounds {
+            Err(Error::RoundOutOfOrder(j, self.rounds))
+        } else if self.expect != g_j.sum_over_boolean() {
+            Err(Error::ClaimMismatch(j, self.expect, g_j.sum_over_boolean()))
         } else if self.r.len() == (self.n - 1) {
             // Last round
             self.r.push(r_j);
-            if let Some(g) = &self.g {
-                Ok(VerifierRoundResult::FinalRound(p0 + r_j * (p1 - p0) == g.evaluate(&self.r).unwrap()))
-            } else {
-                Err(Error::NoPolySet)
-            }
+            let point = self.order.point(&self.r);
+            let oracle = match self.mode {
+                OracleMode::Polynomial => self.g.query(&point)?,
+                OracleMode::MultilinearExtension => {
+                    let cube: Vec<Vec<F>> = BooleanHypercube::new(self.n as u32).collect();
+                    let table = self.g.query_many(&cube)?;
+                    if table.len() != cube.len() {
+                        return Err(Error::WrongOracleAnswerCount(cube.len(), table.len()));
+                    }
+                    evaluate_table(&table, &point)
+                }
+            };
+            Ok(VerifierRoundResult::FinalRound(g_j.evaluate(r_j) == oracle))
         } else {
             self.r.push(r_j);
-            self.expect = p0 + r_j * (p1 - p0);
+            self.expect = g_j.evaluate(r_j);
             Ok(VerifierRoundResult::JthRound(r_j))
         }
     }
 }
+
+/// Checks that `g` can be verified as `options` say, at the soundness they require, and
+/// returns the degree bound of every round. [`Verifier::with_options`] and [`crate::sumcheck_ml::verify`] both start here.
+pub(crate) fn oracle_degree_bounds<F: Field, O: EvaluationOracle<F>>(g: &O, options: &VerifierOptions) -> Result<Vec<usize>, Error<F>> {
+    let num_vars = g.num_vars();
+    if num_vars == 0 {
+        return Err(Error::EmptyInput);
+    }
+    if options.mode == OracleMode::Polynomial {
+        if let Some((var, degree)) = g.degrees().into_iter().enumerate().find(|(_, d)| *d > 1) {
+            return Err(Error::NotMultilinear(var, degree));
+        }
+    }
+    options.security.check(ml_soundness_bits::<F>(num_vars))?;
+    Ok(vec![1; num_vars])
+}
@@ -1,189 +1,24 @@
-#[allow(dead_code)]
-use std::marker::PhantomData;
-use ark_ff::Zero;
-use ark_ff::{
-	fields::Fp64,
-	fields::{MontBackend, MontConfig},
-	Field, One, PrimeField,
-};
-use ark_poly::{
-    multivariate::{self, SparseTerm, Term},
-    DenseMVPolynomial,
-    univariate, Polynomial,
-};
-use ark_std::{rand::Rng, test_rng};
-use bitvec::slice::BitSlice;
-
-pub trait RngF<F> {
-    fn draw(&mut self) -> F;
-}
-
-impl<F: Field, T: Rng> RngF<F> for T {
-    fn draw(&mut self) -> F {
-		let t = F::rand(self);
-		// println!("RngF::draw()# {:?} {:?}",&t,&t.to_string());
-        t
-    }
-}
-
-/// An error type of sum check protocol
-#[derive(Debug, thiserror::Error)]
-pub enum Error {
-    #[error("prover claim mismatches evaluation {0} {1}")]
-    ProverClaimMismatch(String, String),
-
-    #[error("verifier has no oracle access to the polynomial")]
-    NoPolySet,
-}
-
-pub struct BooleanHypercube<F: Field> {
-    n: u32,
-    current: u64,
-    __f: PhantomData<F>,
-}
-
-impl<F: Field> BooleanHypercube<F> {
-
-    pub fn new(n: u32) -> Self {
-        Self {
-            n,
-            current: 0,
-            __f: PhantomData,
-        }
-    }
-}
-
-impl<F: Field> Iterator for BooleanHypercube<F> {
-    type Item = Vec<F>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current == 2u64.pow(self.n) {
-            None
-        } else {
-            let vec = self.current.to_le_bytes();
-            let s: &BitSlice<u8> = BitSlice::try_from_slice(&vec).unwrap();
-            self.current += 1;
-
-            Some(
-                s.iter()
-                    .take(self.n as usize)
-                    .map(|f| match *f {
-                        false => F::zero(),
-                        true => F::one(),
-                    })
-                    .collect(),
-            )
-        }
-    }
-}
+use std::sync::Arc;
 
-pub trait SumCheckPolynomial<F: Field> {
-    /// Evaluates `self` at a given point
-    fn evaluate(&self, point: &[F]) -> Option<F>;
+use ark_ff::Field;
+#[cfg(test)]
+use ark_poly::{multivariate::{self, SparseTerm, Term}, DenseMVPolynomial};
+use ark_poly::univariate;
+use ark_std::test_rng;
 
-    /// Reduce the number of variables in `Self` by fixing a
-    /// `partial_point.len()` variables at `partial_point`.
-    fn fix_variables(&self, partial_point: &[F]) -> Self;
-
-    fn to_univariate(&self) -> univariate::SparsePolynomial<F>;
-
-    /// Returns the number of variables in `self`
-    fn num_vars(&self) -> usize;
-
-    /// Returns a list of evaluations over the entire BooleanHypercube domain
-    fn to_evaluations(&self) -> Vec<F>;
-}
-
-impl<F: Field> SumCheckPolynomial<F> for multivariate::SparsePolynomial<F, SparseTerm> {
-    fn evaluate(&self, point: &[F]) -> Option<F> {
-        Some(Polynomial::evaluate(self, &point.to_owned()))
-    }
-
-    fn fix_variables(&self, partial_point: &[F]) -> Self {
-		// println!("{:?}",&partial_point);
-        let mut res = Self::zero();
-        let num_vars = DenseMVPolynomial::num_vars(self);
-        let mut full_point = partial_point.to_vec();
-        full_point.append(&mut vec![F::one(); num_vars - partial_point.len()]);
-
-        for (coeff, term) in self.terms() {
-            let mut eval = term.evaluate(&full_point);
-            eval *= coeff;
-            let new_term = SparseTerm::new(
-                term.iter()
-                    .filter(|(var, _)| *var >= partial_point.len())
-                    .map(|(var, power)| (var - partial_point.len(), *power))
-                    .collect(),
-            );
-            let poly = multivariate::SparsePolynomial {
-                num_vars: num_vars - partial_point.len(),
-                terms: vec![(eval, new_term)],
-            };
-
-            res += &poly;
-        }
-
-        res
-    }
-
-    fn to_univariate(&self) -> univariate::SparsePolynomial<F> {
-        let mut res = univariate::SparsePolynomial::zero();
-		// println!("Prover::to_uni()# Boolean Hyper cube {:?} ",(DenseMVPolynomial::num_vars(self) - 1) as u32);
-
-        for p in BooleanHypercube::new((DenseMVPolynomial::num_vars(self) - 1) as u32) {
-            let mut point = vec![F::one()];
-			// println!("Prover::to_uni()# p {:?}",&p);
-			// println!("Prover::to_uni()# b4 {:?}",&point);
-            point.extend_from_slice(&p);
-			// println!("Prover::to_uni()# a4 {:?}",&point);
-            let mut r = univariate::SparsePolynomial::zero();
-
-            for (coeff, term) in self.terms() {
-				// println!("----");
-				// println!("Prover::to_uni()# I coeff,term {:?} {:?}",&coeff,&term);
-                let mut eval = term.evaluate(&point);
-				// println!("Prover::to_uni()# II eval {:?}",&eval);
-				
-				
-                let power = term
-                    .iter()
-                    .find(|(variable, _power)| *variable == 0)
-                    .map(|(_variable, power)| *power)
-                    .unwrap_or(0);
-
-                eval *= coeff;
-
-				// println!("Prover::to_uni()# III power eval term {:?} {:?} {:?}",&power,&eval,&term);
-				
-				
-				
-                r += &univariate::SparsePolynomial::from_coefficients_slice(&[(power, eval)]);
-				// println!("Prover::to_uni()# III r {:?}",&r);
-            }
-
-            res += &r;
-        }
-		// println!("Prover::to_uni()# end {:?}",&res);
-		// todo!();
-        res
-    }
-
-    fn num_vars(&self) -> usize {
-        DenseMVPolynomial::num_vars(self)
-    }
-
-    fn to_evaluations(&self) -> Vec<F> {
-        BooleanHypercube::new(DenseMVPolynomial::num_vars(self) as u32)
-            .map(|point| Polynomial::evaluate(self, &point))
-            .collect()
-    }
-	
-}
+use crate::error::{check_prover_round, Error};
+use crate::fiat_shamir::{check_oracle, verify_batch_with, verify_rounds, BatchReport, Proof, SubClaim};
+use crate::oracle::EvaluationOracle;
+use crate::poly::{RngF, SumCheckPolynomial};
+use crate::round_poly::RoundPolynomial;
+use crate::soundness::{naive_soundness_bits, SecurityConfig};
 
 /// The state of the Prover.
+#[derive(Clone)]
 pub struct Prover<F: Field, P: SumCheckPolynomial<F>> {
-    /// $g$ a polynomial being used in this run of the protocol.
-    g: P,
+    /// $g$ a polynomial being used in this run of the protocol, shared by clones of the
+    /// prover until they fix a variable.
+    g: Arc<P>,
 
     /// $C_1$ a value prover _claims_ equal the true answer.
     c_1: F,
@@ -195,16 +30,20 @@ pub struct Prover<F: Field, P: SumCheckPolynomial<F>> {
 }
 
 impl<F: Field, P: SumCheckPolynomial<F>> Prover<F, P> {
-    /// Create a new [`Prover`] state with the polynomial $g$.
-    pub fn new(g: P) -> Self {
-        let c_1 = g.to_evaluations().into_iter().sum();
+    /// Create a new [`Prover`] state with the polynomial $g$, which must have at least
+    /// one variable.
+    pub fn new(g: P) -> Result<Self, Error<F>> {
         let num_vars = g.num_vars();
-        Self {
-            g,
+        if num_vars == 0 {
+            return Err(Error::EmptyInput);
+        }
+        let c_1 = g.to_evaluations().into_iter().sum();
+        Ok(Self {
+            g: Arc::new(g),
             c_1,
             num_vars,
             r: Vec::with_capacity(num_vars),
-        }
+        })
     }
 
     /// Get the value $C_1$ that prover claims equal true answer.
@@ -212,19 +51,29 @@ impl<F: Field, P: SumCheckPolynomial<F>> Prover<F, P> {
         self.c_1
     }
 
-    /// Perform $j$-th round of the [`Prover`] side of the prococol.
-    pub fn round(&mut self, r_prev: F, j: usize) -> univariate::SparsePolynomial<F> {
+    /// Checks the public $C_1$ against [`Prover::c_1`].
+    pub fn for_claim(self, c_1: F) -> Result<Self, Error<F>> {
+        if c_1 != self.c_1 {
+            return Err(Error::FalseClaim(c_1, self.c_1));
+        }
+        Ok(self)
+    }
+
+    /// Perform $j$-th round of the [`Prover`] side of the prococol. Fails unless $j$ is the
+    /// next round.
+    pub fn round(&mut self, r_prev: F, j: usize) -> Result<univariate::SparsePolynomial<F>, Error<F>> {
+        check_prover_round(j, &self.r, self.num_vars)?;
 		// println!("Prover::round()# r_prev,j {:?} {:?}",&r_prev,&j);
         if j != 0 {
             self.r.push(r_prev);
-            self.g = self.g.fix_variables(&[r_prev]);
+            self.g = Arc::new(self.g.fix_variables(&[r_prev]));
         }
 
 		// println!("------------------------------------------");
 		// println!("Prover::round()# self.g.to_univariate() {:?} ",&self.g.to_univariate());
 		// println!("------------------------------------------");
 
-        self.g.to_univariate()
+        Ok(self.g.to_univariate())
     }
 
     pub fn num_vars(&self) -> usize {
@@ -233,7 +82,7 @@ impl<F: Field, P: SumCheckPolynomial<F>> Prover<F, P> {
 }
 
 /// The state of the Verifier.
-pub struct Verifier<F: Field, P: SumCheckPolynomial<F>> {
+pub struct Verifier<F: Field, O: EvaluationOracle<F>> {
     /// Number of variables in the original polynomial.
     n: usize,
 
@@ -241,13 +90,16 @@ pub struct Verifier<F: Field, P: SumCheckPolynomial<F>> {
     c_1: F,
 
     /// Univariate polynomials $g_1,...,g_{j-1}$ received from the [`Prover`].
-    g_part: Vec<univariate::SparsePolynomial<F>>,
+    g_part: Vec<RoundPolynomial<F>>,
 
     /// Previously picked random values $r_1,...,r_{j-1}$.
     r: Vec<F>,
 
-    /// Original polynomial for oracle access
-    g: Option<P>,
+    /// Oracle access to the original polynomial
+    g: O,
+
+    /// Upper bounds $d_1,...,d_n$ on the degree of $g_j$.
+    degrees: Vec<usize>,
 }
 
 /// Values returned by Validator as a result of its run on every step.
@@ -257,26 +109,84 @@ pub enum VerifierRoundResult<F: Field> {
     FinalRound(bool),
 }
 
-impl<F: Field, P: SumCheckPolynomial<F>> Verifier<F, P> {
-    /// Create the new state of the [`Verifier`].
+/// How a [`Verifier`] runs, see [`Verifier::with_options`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VerifierOptions {
+    /// Upper bounds $d_1,...,d_n$ on the degree of $g_j$, the degrees of $g$ if `None`.
+    pub degree_bounds: Option<Vec<usize>>,
+
+    /// Soundness the verifier refuses to run below.
+    pub security: SecurityConfig,
+}
+
+impl VerifierOptions {
+    /// Checks the round polynomials against the given per-variable degree bounds instead
+    /// of the degrees of $g$.
+    pub fn degree_bounds(mut self, degrees: Vec<usize>) -> Self {
+        self.degree_bounds = Some(degrees);
+        self
+    }
+
+    /// Refuses to run if the protocol does not reach the soundness required by `config`
+    /// instead of [`SecurityConfig::default`].
+    pub fn security(mut self, config: SecurityConfig) -> Self {
+        self.security = config;
+        self
+    }
+
+    /// Runs even below the required soundness, see [`SecurityConfig::allow_insecure`].
+    pub fn allow_insecure(mut self) -> Self {
+        self.security = self.security.allow_insecure();
+        self
+    }
+}
+
+impl<F: Field, O: EvaluationOracle<F>> Verifier<F, O> {
+    /// Create the new state of the [`Verifier`], which refuses to run below the soundness
+    /// [`SecurityConfig::default`] requires.
     /// $n$ - degree of the polynomial
     /// $C_1$ - the value claimed to be true answer by the [`Prover`].
-    /// $g$ - the polynimial itself for oracle access by the [`Verifier`].
-    pub fn new(g: Option<P>) -> Self {
-		let num_vars = g.as_ref().unwrap().num_vars();
-        Self {
+    /// $g$ - oracle access to the polynomial for the [`Verifier`], e.g. the polynomial itself.
+    pub fn new(g: O) -> Result<Self, Error<F>> {
+        Self::with_options(g, VerifierOptions::default())
+    }
+
+    /// Same as [`Verifier::new`], running as `options` say.
+    pub fn with_options(g: O, options: VerifierOptions) -> Result<Self, Error<F>> {
+		let degrees = oracle_degree_bounds(&g, &options)?;
+        let num_vars = degrees.len();
+        Ok(Self {
             n:num_vars,
             c_1: F::zero(),
             g_part: Vec::with_capacity(num_vars),
             r: Vec::with_capacity(num_vars),
             g,
-        }
+            degrees,
+        })
+    }
+
+    /// Returns the number of variables, which is also the number of rounds.
+    pub fn num_vars(&self) -> usize {
+        self.n
+    }
+
+    /// Same as [`Verifier::with_options`], starting from the claim $C_1$, e.g. one received
+    /// from a third party rather than from the [`Prover`].
+    pub fn with_claim(g: O, c_1: F, options: VerifierOptions) -> Result<Self, Error<F>> {
+        let mut verifier = Self::with_options(g, options)?;
+        verifier.set_c_1(c_1);
+        Ok(verifier)
     }
 
     pub fn set_c_1(&mut self, c_1: F) {
         self.c_1 = c_1;
     }
 
+    /// Returns the oracle, e.g. to read what a [`crate::oracle::CountingOracle`] counted.
+    pub fn oracle(&self) -> &O {
+        &self.g
+    }
+
     /// Perform the $j$-th round of the [`Verifier`] side of the protocol.
     ///
     /// $g_j$ - a univariate polynomial sent in this round by the [`Prover`].
@@ -284,54 +194,40 @@ impl<F: Field, P: SumCheckPolynomial<F>> Verifier<F, P> {
         &mut self,
         g_j: univariate::SparsePolynomial<F>,
         rng: &mut R,
-    ) -> Result<VerifierRoundResult<F>, Error> {
+    ) -> Result<VerifierRoundResult<F>, Error<F>> {
 		let r_j = rng.draw();
-		println!("V::round()# j g_j {:?} {:?} {:?}",self.r,&r_j,g_j);
-		println!("---");
-        if self.r.is_empty() {
-            // First Round
-            let evaluation = g_j.evaluate(&F::zero()) + g_j.evaluate(&F::one());
-			println!("V::round()# 1st round eval,c {:?} {:?}",evaluation,self.c_1);
-            if self.c_1 != evaluation {
-                Err(Error::ProverClaimMismatch(
-                    format!("start {:?}", self.c_1),
-                    format!("{:?}", evaluation),
-                ))
-            } else {
-                self.g_part.push(g_j);
-                self.r.push(r_j);
-
-                Ok(VerifierRoundResult::JthRound(r_j))
-            }
-        } else if self.r.len() == (self.n - 1) {
+
+        let j = self.r.len();
+        if j == self.n {
+            return Err(Error::RoundOutOfOrder(j, self.n));
+        }
+
+        // A polynomial of higher degree gives the prover more freedom than the
+        // Schwartz-Zippel bound accounts for.
+        let g_j = RoundPolynomial::from(g_j);
+        let degree = g_j.degree();
+        if degree > self.degrees[j] {
+            return Err(Error::DegreeTooHigh(j, degree, self.degrees[j]));
+        }
+
+        // The first round is checked against $C_1$, every later one against $g_{j-1}(r_{j-1})$.
+        let expected = match (self.g_part.last(), self.r.last()) {
+            (Some(g_jprev), Some(r_jprev)) => g_jprev.evaluate(*r_jprev),
+            _ => self.c_1,
+        };
+        let evaluation = g_j.sum_over_boolean();
+        if expected != evaluation {
+            return Err(Error::ClaimMismatch(j, expected, evaluation));
+        }
+
+        if self.r.len() == (self.n - 1) {
             // Last round
             self.r.push(r_j);
 
-            if let Some(g) = &self.g {
-                assert_eq!(g_j.evaluate(&r_j), g.evaluate(&self.r).unwrap());
-				// println!("V::round()# last g_j,g {:?} {:?}",g_j.evaluate(&r_j),g.evaluate(&self.r).unwrap());
-
-                Ok(VerifierRoundResult::FinalRound(
-                    g_j.evaluate(&r_j) == g.evaluate(&self.r).unwrap(),
-                ))
-            } else {
-                Err(Error::NoPolySet)
-            }
+            let oracle = self.g.query(&self.r)?;
+            Ok(VerifierRoundResult::FinalRound(g_j.evaluate(r_j) == oracle))
         } else {
             // j-th round
-            let g_jprev = self.g_part.last().unwrap();
-            let r_jprev = self.r.last().unwrap();
-
-            let prev_evaluation = g_jprev.evaluate(r_jprev);
-            let evaluation = g_j.evaluate(&F::zero()) + g_j.evaluate(&F::one());
-			// println!("V::round()# j rprev_evaluation,evaluation {:?} {:?}",prev_evaluation,evaluation);
-            if prev_evaluation != evaluation {
-                return Err(Error::ProverClaimMismatch(
-                    format!("{:?}", prev_evaluation),
-                    format!("{:?}", evaluation),
-                ));
-            }
-			
             self.g_part.push(g_j);
             self.r.push(r_j);
 
@@ -340,44 +236,64 @@ impl<F: Field, P: SumCheckPolynomial<F>> Verifier<F, P> {
     }
 }
 
-#[test]
-fn test_normal_poly(){
-	// #[derive(MontConfig)]
-    // #[modulus = "71"]
-    // #[generator = "7"]
-    // struct FrConfig;
-
-	// https://cronokirby.com/notes/2022/09/the-goldilocks-field/
-	// #[derive(MontConfig)]
-    // #[modulus = "18446744069414584321"]
-    // #[generator = "7"]
-    // struct FrConfig;
-
-    #[derive(MontConfig)]
-    #[modulus = "97"]
-    #[generator = "5"]
-    struct FrConfig;
-
+/// Checks a whole [`Proof`] that $g$ sums to `claim`, as made by
+/// [`crate::fiat_shamir::prove`] from a [`Prover`], and its last round against $g$.
+/// `security` is enforced as in [`Verifier::with_options`], before any round.
+pub fn verify<F: Field, O: EvaluationOracle<F>>(
+    proof: &Proof<univariate::SparsePolynomial<F>>,
+    claim: F,
+    g: &mut O,
+    security: SecurityConfig,
+) -> Result<SubClaim<F>, Error<F>> {
+    let subclaim = verify_rounds(proof, claim, &oracle_degree_bounds(g, &VerifierOptions::default().security(security))?)?;
+    let value = g.query(&subclaim.point)?;
+    check_oracle(subclaim, value)
+}
 
-    type GL64 = Fp64<MontBackend<FrConfig, 1>>;
-    type Fp97 = Fp64<MontBackend<FrConfig, 1>>;
+/// Same as [`verify`] for many proofs about $g$ with their claims. The rounds of every
+/// proof are checked on their own, only the final queries are shared: $g$ answers all of
+/// them in one [`EvaluationOracle::query_many`].
+pub fn verify_batch<F: Field, O: EvaluationOracle<F>>(
+    proofs: &[(Proof<univariate::SparsePolynomial<F>>, F)],
+    g: &mut O,
+    security: SecurityConfig,
+) -> Result<BatchReport<F>, Error<F>> {
+    let degrees = oracle_degree_bounds(g, &VerifierOptions::default().security(security))?;
+    verify_batch_with(proofs, &degrees, |points| g.query_many(points))
+}
 
+/// Checks that `g` can be verified as `options` say, at the soundness they require, and
+/// returns the degree bound of every round. [`Verifier::with_options`] and [`verify`] both start here.
+fn oracle_degree_bounds<F: Field, O: EvaluationOracle<F>>(g: &O, options: &VerifierOptions) -> Result<Vec<usize>, Error<F>> {
+    let num_vars = g.num_vars();
+    if num_vars == 0 {
+        return Err(Error::EmptyInput);
+    }
+    let degrees = options.degree_bounds.clone().unwrap_or_else(|| g.degrees());
+    if degrees.len() != num_vars {
+        return Err(Error::NumVarsMismatch(num_vars, degrees.len()));
+    }
+    options.security.check(naive_soundness_bits::<F>(&degrees))?;
+    Ok(degrees)
+}
 
+#[cfg(test)]
+fn normal_poly<F: Field>(){
 	let rng = &mut test_rng();
         // 2 *x_0^3 + x_0 * x_2 + x_1 * x_2
 	// let g: multivariate::SparsePolynomial<_, SparseTerm> = multivariate::SparsePolynomial::from_coefficients_slice(
 	// 	3,
 	// 	&[
 	// 		(
-	// 			GL64::from_bigint(2u32.into()).unwrap(),
+	// 			Fp97::from_bigint(2u32.into()).unwrap(),
 	// 			multivariate::SparseTerm::new(vec![(0, 3)]),
 	// 		),
 	// 		(
-	// 			GL64::from_bigint(1u32.into()).unwrap(),
+	// 			Fp97::from_bigint(1u32.into()).unwrap(),
 	// 			multivariate::SparseTerm::new(vec![(0, 1), (2, 1)]),
 	// 		),
 	// 		(
-	// 			GL64::from_bigint(1u32.into()).unwrap(),
+	// 			Fp97::from_bigint(1u32.into()).unwrap(),
 	// 			multivariate::SparseTerm::new(vec![(1, 1), (2, 1)]),
 	// 		),
 	// 	],
@@ -386,27 +302,27 @@ fn test_normal_poly(){
 		2,
 		&[
 			(
-				Fp97::from_bigint(20u32.into()).unwrap(),
+				F::from(20u32),
 				multivariate::SparseTerm::new(vec![(0, 2)]),
 			),
 			(
-				Fp97::from_bigint(5u32.into()).unwrap(),
+				F::from(5u32),
 				multivariate::SparseTerm::new(vec![(0, 2), (1, 1)]),
 			),
 			(
-				Fp97::from_bigint(29u32.into()).unwrap(),
+				F::from(29u32),
 				multivariate::SparseTerm::new(vec![(0, 1), (1, 1)]),
 			),
             (
-				Fp97::from_bigint(62u32.into()).unwrap(),
+				F::from(62u32),
 				multivariate::SparseTerm::new(vec![(0, 2), (1, 2)]),
 			),
             (
-				Fp97::from_bigint(90u32.into()).unwrap(),
+				F::from(90u32),
 				multivariate::SparseTerm::new(vec![(0, 1), (1, 2)]),
 			),
             (
-				Fp97::from_bigint(88u32.into()).unwrap(),
+				F::from(88u32),
 				multivariate::SparseTerm::new(vec![(1, 2)]),
 			),
 		],
@@ -429,43 +345,78 @@ fn test_normal_poly(){
 	// 		),
 	// 	],
 	// );
-	println!("=============== Given Polynomial ===================");
-	println!("{:?}",g.clone());
-	println!("==========================================================");
-	let mut prover = Prover::new(g.clone());
-	let c_1 = prover.c_1();
-	println!("claim H => {:?}",c_1);
-	let mut r_j = Fp97::one();
-	let mut verifier = Verifier::new(Some(g));
-	verifier.set_c_1(c_1);
-
-	for j in 0..3 {
-		let g_j = prover.round(r_j, j);
-		let verifier_res = verifier.round(g_j, rng).unwrap();
-		match verifier_res {
-			VerifierRoundResult::JthRound(r) => {
-                println!("match :: round r {:?} {:?} ", j,r);
-				r_j = r;
-			}
-			VerifierRoundResult::FinalRound(res) => {
-				assert!(res);
-				break;
-			}
-		}
-	}
+	let prover = Prover::new(g.clone()).unwrap();
+	let verifier = |c_1| {
+		let mut verifier = Verifier::with_options(g, VerifierOptions::default().allow_insecure()).unwrap();
+		verifier.set_c_1(c_1);
+		verifier
+	};
+	let outcome = crate::protocol::run_protocol(prover, verifier, rng).unwrap();
+	assert!(outcome.accepted);
+}
+
+#[test]
+fn test_normal_poly(){
+    for_each_field!(F => normal_poly::<F>());
+}
+
+#[test]
+fn test_degree_bound(){
+    use ark_ff::One;
+    use crate::fields::Fp97;
+    use crate::parser::parse_polynomial;
+
+    // degree 2 in x_0 and 1 in x_1
+    let g = parse_polynomial::<Fp97>("20*x0^2 + 5*x0^2*x1 + 29*x0*x1 + 3*x1").unwrap();
+    let rng = &mut test_rng();
+    let mut prover = Prover::new(g.clone()).unwrap();
+
+    let mut verifier = Verifier::with_options(g.clone(), VerifierOptions::default().allow_insecure()).unwrap();
+    verifier.set_c_1(prover.c_1());
+    // g_1 + (x^3 - x) still sums to $C_1$ but exceeds the degree of $x_0$
+    let g_1 = prover.round(Fp97::one(), 0).unwrap();
+    let tampered = &g_1 + &univariate::SparsePolynomial::from_coefficients_vec(vec![(1, -Fp97::one()), (3, Fp97::one())]);
+    assert!(matches!(verifier.round(tampered, rng), Err(Error::DegreeTooHigh(0, 3, 2))));
+
+    let mut verifier = Verifier::with_options(g, VerifierOptions::default().allow_insecure().degree_bounds(vec![1, 1])).unwrap();
+    verifier.set_c_1(prover.c_1());
+    assert!(matches!(verifier.round(g_1, rng), Err(Error::DegreeTooHigh(0, 2, 1))));
+}
+
+#[test]
+fn test_public_claim(){
+    use ark_ff::One;
+    use crate::fields::Fp97;
+    use crate::parser::parse_polynomial;
+    use crate::protocol::run_protocol_on_claim;
+
+    let rng = &mut test_rng();
+    let g = parse_polynomial::<Fp97>("20*x0^2 + 5*x0^2*x1 + 29*x0*x1").unwrap();
+    let public = Prover::new(g.clone()).unwrap().c_1();
+
+    // a claim from a third party, with options the verifier is built with
+    let options = VerifierOptions::default().allow_insecure().degree_bounds(vec![2, 1]);
+    let verifier = |c_1| Verifier::with_claim(g.clone(), c_1, options.clone()).unwrap();
+
+    let prover = Prover::new(g.clone()).unwrap().for_claim(public).unwrap();
+    assert!(run_protocol_on_claim(prover, public, verifier, rng).unwrap().accepted);
+
+    let false_claim = public + Fp97::one();
+    assert!(matches!(Prover::new(g.clone()).unwrap().for_claim(false_claim), Err(Error::FalseClaim(..))));
+    let res = run_protocol_on_claim(Prover::new(g.clone()).unwrap(), false_claim, verifier, rng);
+    assert!(matches!(res, Err(Error::ClaimMismatch(0, ..))));
+    assert!(matches!(Verifier::with_claim(g.clone(), public, VerifierOptions::default()), Err(Error::InsufficientSecurity(..))));
 }
 
+pub fn prove_bench<F: Field, P: SumCheckPolynomial<F>>(g:P) -> Result<(), Error<F>> {
+    let mut prover = Prover::new(g)?;
 
-pub fn prove_bench<F: Field, P: SumCheckPolynomial<F>>(g:P){
-    let mut prover = Prover::new(g);
-    // let mut verifier = Verifier::new(Some(g),prover.claim());
-    
     let rng = &mut test_rng();
     let mut r_j = F::one();
 
     for j in 0..prover.num_vars() {
-        let p = prover.round(r_j, j);
+        prover.round(r_j, j)?;
         r_j = rng.draw();
     }
+    Ok(())
 }
-fn main(){}
\ No newline at end of file
@@ -0,0 +1,170 @@
+//! The factors of [`crate::sumcheck_pml`] as tables over a grid, so factors of any degree in
+//! a variable can be folded like multilinear ones.
+use std::sync::Arc;
+
+use ark_ff::Field;
+
+use crate::error::{check_evaluation_points, Error};
+use crate::poly::{BindingOrder, HypercubeEvaluation};
+use crate::round_poly::Interpolator;
+
+/// The values of a factor on $\prod_i \{0, ..., e_i\}$ with $x_0$ varying fastest, where
+/// $e_i \ge 1$ bounds the degree in the $i$-th unbound variable. A multilinear factor is
+/// its table over the hypercube.
+///
+/// Clones share the values until they fold them, so repetitions of a prover build them once.
+#[derive(Debug, Clone)]
+pub(crate) struct Grid<F> {
+    values: Arc<Vec<F>>,
+    degrees: Vec<usize>,
+}
+
+impl<F: Field> Grid<F> {
+    pub(crate) fn new<P: HypercubeEvaluation<F>>(poly: &P) -> Self {
+        let degrees: Vec<usize> = poly.degrees().into_iter().map(|d| d.max(1)).collect();
+        let values = if degrees.iter().all(|e| *e == 1) {
+            poly.to_evaluations()
+        } else {
+            let len = degrees.iter().map(|e| e + 1).product();
+            let mut point = vec![F::zero(); degrees.len()];
+            (0..len)
+                .map(|mut index| {
+                    for (x, e) in point.iter_mut().zip(&degrees) {
+                        *x = F::from((index % (e + 1)) as u64);
+                        index /= e + 1;
+                    }
+                    poly.evaluate(&point).unwrap()
+                })
+                .collect()
+        };
+        Self { values: Arc::new(values), degrees }
+    }
+
+    /// Returns the values over the grid.
+    pub(crate) fn values(&self) -> &[F] {
+        &self.values
+    }
+
+    /// Returns the position of the variable bound next among the unbound ones.
+    fn axis(&self, order: BindingOrder) -> usize {
+        match order {
+            BindingOrder::LowToHigh => 0,
+            BindingOrder::HighToLow => self.degrees.len() - 1,
+        }
+    }
+
+    /// Returns the first index and the stride of line `k` along the next variable, `k`
+    /// indexing the other variables in the layout they keep after the fold.
+    fn line(&self, order: BindingOrder, k: usize) -> (usize, usize) {
+        let (step, stride) = self.strides(order);
+        (k * step, stride)
+    }
+
+    /// Returns how far apart the first indices of consecutive lines along the next variable
+    /// are, and the stride within a line.
+    fn strides(&self, order: BindingOrder) -> (usize, usize) {
+        let e = self.degrees[self.axis(order)];
+        match order {
+            BindingOrder::LowToHigh => (e + 1, 1),
+            BindingOrder::HighToLow => (1, self.values.len() / (e + 1)),
+        }
+    }
+
+    /// Returns the line through the point of $\{0,1\}$ for the other variables whose bit $i$
+    /// gives the $i$-th of them.
+    pub(crate) fn line_index(&self, order: BindingOrder, mask: usize) -> usize {
+        // on the hypercube the layout after the fold is the mask itself
+        if self.values.len() == 1 << self.degrees.len() {
+            return mask;
+        }
+        let axis = self.axis(order);
+        let (mut index, mut stride, mut bit) = (0, 1, 0);
+        for (_, e) in self.degrees.iter().enumerate().filter(|(i, _)| *i != axis) {
+            index += (mask >> bit & 1) * stride;
+            stride *= e + 1;
+            bit += 1;
+        }
+        index
+    }
+
+    /// Writes the values of line `k` at $0, 1, ..., out.len() - 1$ into `out`.
+    pub(crate) fn evaluate_line(&self, order: BindingOrder, k: usize, out: &mut [F]) {
+        let e = self.degrees[self.axis(order)];
+        let (start, stride) = self.line(order, k);
+        if e == 1 {
+            let (mut value, step) = (self.values[start], self.values[start + stride] - self.values[start]);
+            for o in out.iter_mut() {
+                *o = value;
+                value += step;
+            }
+        } else {
+            let line: Vec<F> = (0..=e).map(|m| self.values[start + m * stride]).collect();
+            let interpolator = Interpolator::new(e + 1);
+            for (t, o) in out.iter_mut().enumerate() {
+                *o = line.get(t).copied().unwrap_or_else(|| interpolator.evaluate(&line, F::from(t as u64)));
+            }
+        }
+    }
+
+    /// Binds the next variable to `r`. Values still shared with clones are left to them and
+    /// the folded ones written anew.
+    pub(crate) fn fold(&mut self, order: BindingOrder, r: F) {
+        let axis = self.axis(order);
+        let e = self.degrees[axis];
+        let lines = self.values.len() / (e + 1);
+        let (step, stride) = self.strides(order);
+        let mut line = Vec::with_capacity(e + 1);
+        let interpolator = Interpolator::new(e + 1);
+        let mut fold = |values: &[F], k: usize| {
+            let start = k * step;
+            if e == 1 {
+                values[start] + (values[start + stride] - values[start]) * r
+            } else {
+                line.clear();
+                line.extend((0..=e).map(|m| values[start + m * stride]));
+                interpolator.evaluate(&line, r)
+            }
+        };
+        match Arc::get_mut(&mut self.values) {
+            Some(values) => {
+                // entry $k$ is written only after every line up to $k$ has been read
+                for k in 0..lines {
+                    values[k] = fold(values, k);
+                }
+                values.truncate(lines);
+            }
+            None => self.values = Arc::new((0..lines).map(|k| fold(&self.values, k)).collect()),
+        }
+        self.degrees.remove(axis);
+    }
+}
+
+/// Returns the degree of the product of the factors $g$ in each variable.
+pub(crate) fn product_degrees<F: Field, P: HypercubeEvaluation<F>>(g: &[P]) -> Vec<usize> {
+    let mut degrees = vec![0; g.first().map_or(0, |f| f.num_vars())];
+    for f in g {
+        for (d, d_f) in degrees.iter_mut().zip(f.degrees()) {
+            *d += d_f;
+        }
+    }
+    degrees
+}
+
+/// Returns the degree of the round polynomial of every round when binding in `order`.
+pub(crate) fn round_degrees(degrees: &[usize], order: BindingOrder) -> Vec<usize> {
+    (0..degrees.len()).map(|j| degrees[order.variable(j, degrees.len())]).collect()
+}
+
+/// Returns the number of variables shared by the factors $g$, checking that the field has
+/// the points the round polynomials are evaluated at.
+pub(crate) fn check_factors<F: Field, P: HypercubeEvaluation<F>>(g: &[P]) -> Result<usize, Error<F>> {
+    let num_vars = g.first().ok_or(Error::EmptyInput)?.num_vars();
+    if num_vars == 0 {
+        return Err(Error::EmptyInput);
+    }
+    if let Some(poly) = g.iter().find(|poly| poly.num_vars() != num_vars) {
+        return Err(Error::NumVarsMismatch(num_vars, poly.num_vars()));
+    }
+    check_evaluation_points(product_degrees(g).into_iter().max().unwrap_or(0) + 1)?;
+    Ok(num_vars)
+}
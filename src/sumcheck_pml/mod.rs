@@ -1,54 +1,50 @@
 pub mod prover;
 pub mod verifier;
-pub mod poly;
+mod grid;
 
 #[cfg(test)]
 mod test;
 
-use ark_std::{rand::Rng, test_rng};
-use ark_ff::{Field, Zero};
-use ark_poly::{
-    multivariate::{self,SparsePolynomial,SparseTerm, Term},
-    DenseMVPolynomial,
-};
-use ark_ff::{
-	fields::Fp64,
-	fields::{MontBackend, MontConfig},
-	One, PrimeField,
-};
+use ark_std::test_rng;
+use ark_ff::Field;
 
 
+use crate::error::Error;
+use crate::fiat_shamir::{check_oracle, verify_batch_with, verify_rounds, BatchReport, Proof, SubClaim};
 use crate::sumcheck_pml::prover::*;
-use crate::sumcheck_pml::verifier::*;
-use crate::sumcheck_pml::poly::*;
+use crate::sumcheck_pml::verifier::{oracle_degree_bounds, VerifierOptions};
+use crate::oracle::EvaluationOracle;
+use crate::soundness::SecurityConfig;
+use crate::poly::{HypercubeEvaluation, RngF};
 
-#[derive(MontConfig)]
-#[modulus = "97"]
-#[generator = "5"]
-struct FrConfig;
-type Fp97 = Fp64<MontBackend<FrConfig, 1>>;
-
-pub trait RngF<F> {
-    fn draw(&mut self) -> F;
-}
-
-impl<F: Field, T: Rng> RngF<F> for T {
-    fn draw(&mut self) -> F {
-		let t = F::rand(self);
-		// println!("RngF::draw()# {:?} {:?}",&t,&t.to_string());
-        t
-    }
-}
-
-pub fn prove_bench<F: Field, P: SumCheckPolynomial<F>>(g:Vec<P>){
-    let mut prover = Prover::new(g);
+pub fn prove_bench<F: Field, P: HypercubeEvaluation<F>>(g:Vec<P>) -> Result<(), Error<F>> {
+    let mut prover = Prover::new(g)?;
     // let mut verifier = Verifier::new(Some(g),prover.claim());
     
     let rng = &mut test_rng();
     let mut r_j = F::one();
 
     for j in 0..prover.num_vars() {
-        let p = prover.round(r_j, j);
+        prover.round(r_j, j)?;
         r_j = rng.draw();
     }
+    Ok(())
+}
+
+/// Checks a whole [`Proof`] that the product $g$ of the factors sums to `claim`, as made
+/// by [`crate::fiat_shamir::prove`] from a [`Prover`] binding in the default order, and its
+/// last round against $g$, e.g. a [`crate::oracle::Product`] of the factors. The soundness
+/// of the round degrees is held against `security` before any round is checked.
+pub fn verify<F: Field, O: EvaluationOracle<F>>(proof: &Proof<Vec<F>>, claim: F, g: &mut O, security: SecurityConfig) -> Result<SubClaim<F>, Error<F>> {
+    let subclaim = verify_rounds(proof, claim, &oracle_degree_bounds(g, &VerifierOptions::default().security(security))?)?;
+    let value = g.query(&subclaim.point)?;
+    check_oracle(subclaim, value)
+}
+
+/// Same as [`verify`] for many proofs about $g$ with their claims. The rounds of every
+/// proof are checked on their own, only the final queries are shared: $g$ answers all of
+/// them in one [`EvaluationOracle::query_many`].
+pub fn verify_batch<F: Field, O: EvaluationOracle<F>>(proofs: &[(Proof<Vec<F>>, F)], g: &mut O, security: SecurityConfig) -> Result<BatchReport<F>, Error<F>> {
+    let degrees = oracle_degree_bounds(g, &VerifierOptions::default().security(security))?;
+    verify_batch_with(proofs, &degrees, |points| g.query_many(points))
 }
@@ -1,70 +1,204 @@
 use ark_ff::Field;
-use crate::sumcheck_pml::poly::*;
-// The state of the Prover.
-pub struct Prover<F: Field, P: SumCheckPolynomial<F>> {
-    g: Vec<P>,  // $g$ a list of polynomial being used in this run of the protocol.
+use crate::error::{check_prover_round, Error};
+use crate::sumcheck_pml::grid::{product_degrees, round_degrees, Grid};
+pub(crate) use crate::sumcheck_pml::grid::check_factors;
+use crate::poly::{BindingOrder, HypercubeEvaluation};
+use crate::round_poly::interpolate_at;
+// The state of the Prover. The factors of $g$ are known only through `table`, so the
+// prover does not depend on their type.
+#[derive(Clone)]
+pub struct Prover<F: Field> {
     claim: F,   // $claim$ a value prover _claim_ equal the true answer.
     r: Vec<F>,  // Random values $r_1,...,r_j$ sent by the [`Verifier`] in the previous rounds.
     num_vars: usize,
-    table:Vec<Vec<F>>   // vector of mle
+    rounds: usize,  // number of rounds to run, less than `num_vars` in a partial run
+    table:Vec<Grid<F>>,   // the factors over their grids
+    degrees: Vec<usize>,  // degree of the round polynomial of every round
+    order: BindingOrder,
 }
 
-impl<F: Field, P: SumCheckPolynomial<F>> Prover<F, P> {
-    /// Create a new [`Prover`] state with the polynomial $g$.
-    pub fn new(g: Vec<P>) -> Self {
+/// What is left of a partial run: the factors with the variables bound so far fixed to the
+/// challenges, over the remaining variables, and the sum their product has.
+///
+/// The remaining variables keep their relative order. A later stage continues with
+/// [`Prover::resume`].
+#[derive(Debug, Clone)]
+pub struct Residual<F: Field> {
+    factors: Vec<Grid<F>>,
+    degrees: Vec<usize>,
+    pub claim: F,
+    /// The challenges of the rounds played, in the order of the rounds.
+    pub challenges: Vec<F>,
+}
 
-        let num_vars = g[0].num_vars();
-        let mut table = Vec::new();
+/// Where a full run ends: the point of $g$ the challenges make up and the value of every
+/// factor there, e.g. for opening commitments to the factors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinalClaim<F: Field> {
+    /// The challenges as a point $(x_0, x_1, ...)$, see [`BindingOrder::point`].
+    pub point: Vec<F>,
+    /// The value of every factor at `point`, in the order the factors were given.
+    pub values: Vec<F>,
+}
 
-        // populate table with mle
-        for poly in g.iter(){
-            assert_eq!(num_vars,poly.num_vars());
-            table.push(poly.to_evaluations());
-        }
+impl<F: Field> Residual<F> {
+    /// Returns the number of remaining variables.
+    pub fn num_vars(&self) -> usize {
+        self.degrees.len()
+    }
 
-        let mut products = vec![F::one();2usize.pow(num_vars as u32)];
+    /// Returns the values of every factor on $\prod_i \{0, ..., e_i\}$ with $x_0$ varying
+    /// fastest, where $e_i$ is the degree of the factor in $x_i$ but at least 1. For a
+    /// multilinear factor this is its table over the hypercube.
+    pub fn tables(&self) -> Vec<&[F]> {
+        self.factors.iter().map(Grid::values).collect()
+    }
+}
 
-        for poly in table.iter(){
-            products = products.iter().zip(poly.iter()).map(|(x, y)| *x * *y).collect::<Vec<F>>();
-        }
+impl<F: Field> Prover<F> {
+    /// Create a new [`Prover`] state with the product of the factors $g$, which must all
+    /// have the same, non-zero number of variables.
+    ///
+    /// Factors may have any degree in each variable. Round $j$ sends the $d_j + 1$
+    /// evaluations of a round polynomial of degree $d_j$, the degree of the product in the
+    /// variable bound in that round.
+    pub fn new<P: HypercubeEvaluation<F>>(g: Vec<P>) -> Result<Self, Error<F>> {
+        Self::with_binding_order(g, BindingOrder::default())
+    }
+
+    /// Same as [`Prover::new`], but binds the variables in the given order.
+    pub fn with_binding_order<P: HypercubeEvaluation<F>>(g: Vec<P>, order: BindingOrder) -> Result<Self, Error<F>> {
 
-        Self {
-            g,
-            claim:products.iter().sum(),
+        let num_vars = check_factors(&g)?;
+        // populate table with the factors over their grids
+        let table = g.iter().map(Grid::new).collect();
+        let degrees = round_degrees(&product_degrees(&g), order);
+
+        let mut prover = Self {
+            claim: F::zero(),
             num_vars,
+            rounds: num_vars,
             r: Vec::with_capacity(num_vars),
-            table
-        }
+            table,
+            degrees,
+            order,
+        };
+        let first = prover.message(0);
+        prover.claim = first[0] + first.get(1).unwrap_or(&first[0]);
+        Ok(prover)
     }
 
     /// Get the value $claim$ that prover claim equal true answer.
     pub fn claim(&self) -> F { self.claim }
 
-    /// Perform $j$-th round of the [`Prover`] side of the prococol.
-    pub fn round(&mut self, r_prev: F, round_j: usize) -> Vec<F> {
+    /// Returns the prover if the product of the factors sums to `claim` over the hypercube,
+    /// so a public claim it cannot prove is refused before the first round.
+    pub fn for_claim(self, claim: F) -> Result<Self, Error<F>> {
+        if claim != self.claim {
+            return Err(Error::FalseClaim(claim, self.claim));
+        }
+        Ok(self)
+    }
 
-        let nv = self.num_vars;     // no of variables
-        let np = self.table.len();  // poly count
-        
+    /// Runs only the first `rounds` rounds, after which [`Prover::into_residual`] hands the
+    /// claim about the remaining variables to the next stage.
+    pub fn partial(mut self, rounds: usize) -> Result<Self, Error<F>> {
+        if rounds == 0 || rounds > self.num_vars {
+            return Err(Error::InvalidRoundCount(rounds, self.num_vars));
+        }
+        self.rounds = rounds;
+        Ok(self)
+    }
+
+    /// Continues from the [`Residual`] of a partial run, binding its variables low to high.
+    /// Fails unless a variable is left and the product sums to the claim.
+    pub fn resume(residual: Residual<F>) -> Result<Self, Error<F>> {
+        let Residual { factors, degrees, claim, .. } = residual;
+        let num_vars = degrees.len();
+        if num_vars == 0 {
+            return Err(Error::EmptyInput);
+        }
+        let prover = Self {
+            claim,
+            num_vars,
+            rounds: num_vars,
+            r: Vec::with_capacity(num_vars),
+            table: factors,
+            degrees,
+            order: BindingOrder::default(),
+        };
+        let first = prover.message(0);
+        let sum = first[0] + first.get(1).unwrap_or(&first[0]);
+        if sum != claim {
+            return Err(Error::FalseClaim(claim, sum));
+        }
+        Ok(prover)
+    }
+
+    /// Binds the variable of the last round played to its challenge `r_last` and returns
+    /// what is left for the remaining variables. Fails with [`Error::InvalidRoundCount`]
+    /// unless the rounds played are exactly [`Prover::num_rounds`].
+    pub fn into_residual(mut self, r_last: F) -> Result<Residual<F>, Error<F>> {
+        if self.r.len() + 1 != self.rounds {
+            return Err(Error::InvalidRoundCount(self.r.len() + 1, self.rounds));
+        }
+        // the sum of the rest is the last round polynomial at the challenge
+        let claim = interpolate_at(&self.message(self.r.len()), r_last);
+        for grid in self.table.iter_mut() {
+            grid.fold(self.order, r_last);
+        }
+        self.r.push(r_last);
+        // the remaining rounds in the order of the variables they bind
+        let degrees = self.order.point(&self.degrees[self.r.len()..]);
+        Ok(Residual { factors: self.table, degrees, claim, challenges: self.r })
+    }
+
+    /// Binds the variable of the last round to its challenge `r_last` and returns the final
+    /// point with the value of every factor at it. Fails with [`Error::InvalidRoundCount`]
+    /// unless every round of the protocol has been played.
+    pub fn finalize(self, r_last: F) -> Result<FinalClaim<F>, Error<F>> {
+        if self.rounds != self.num_vars {
+            return Err(Error::InvalidRoundCount(self.rounds, self.num_vars));
+        }
+        let order = self.order;
+        let residual = self.into_residual(r_last)?;
+        Ok(FinalClaim {
+            point: order.point(&residual.challenges),
+            values: residual.factors.iter().map(|grid| grid.values()[0]).collect(),
+        })
+    }
+
+    /// Perform $j$-th round of the [`Prover`] side of the prococol. Fails unless $j$ is the
+    /// next round and within [`Prover::num_rounds`].
+    pub fn round(&mut self, r_prev: F, round_j: usize) -> Result<Vec<F>, Error<F>> {
+        check_prover_round(round_j, &self.r, self.rounds)?;
         if round_j != 0 {
             self.r.push(r_prev);
             // g.fix_variables(&[r_prev])
-            for j in 0..np{
-                for b in 0..2usize.pow((nv-round_j) as u32){
-                    self.table[j][b] = self.table[j][b << 1] * (F::one() - r_prev) + self.table[j][(b << 1) + 1] * r_prev
-                }
+            for grid in self.table.iter_mut(){
+                grid.fold(self.order, r_prev);
             }
         }
-        let mut product_sum = vec![F::zero();np+1];
-
-        for b in 0..2usize.pow((nv - round_j - 1) as u32){
-            for t in 0..np+1{   // evaluating points
-                let mut product = F::one();
-                for j in 0..np{
-                    let table = &self.table[j];
-                    product *= table[b << 1] * (F::one() - F::from(t as u32)) + table[(b << 1) + 1] * F::from(t as u32);
+        Ok(self.message(round_j))
+    }
+
+    /// Evaluates the round polynomial of round $j$ at $0, 1, ..., d_j$.
+    fn message(&self, round_j: usize) -> Vec<F> {
+        let num_evals = self.degrees[round_j] + 1;
+        let mut product_sum = vec![F::zero();num_evals];
+        let mut product = vec![F::one();num_evals];
+        let mut line = vec![F::zero();num_evals];
+
+        for b in 0..2usize.pow((self.num_vars - round_j - 1) as u32){
+            product.fill(F::one());
+            for grid in self.table.iter(){
+                grid.evaluate_line(self.order, grid.line_index(self.order, b), &mut line);
+                for (p, l) in product.iter_mut().zip(&line){   // evaluating points
+                    *p *= l;
                 }
-                product_sum[t] += product;
+            }
+            for (sum, p) in product_sum.iter_mut().zip(&product){
+                *sum += p;
             }
         }
 
@@ -74,4 +208,9 @@ impl<F: Field, P: SumCheckPolynomial<F>> Prover<F, P> {
     pub fn num_vars(&self) -> usize {
         self.num_vars
     }
+
+    /// Returns the number of rounds to run, all of them unless the run is partial.
+    pub fn num_rounds(&self) -> usize {
+        self.rounds
+    }
 }
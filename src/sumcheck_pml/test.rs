@@ -1,63 +1,249 @@
-use ark_ff::{fields::Fp64,fields::{MontBackend, MontConfig},One, PrimeField,};
-use ark_poly::{multivariate::{self, Term},DenseMVPolynomial, };
+use ark_ff::{Field, One};
+use ark_poly::multivariate::{self, SparseTerm};
 use ark_std::test_rng;
 
+use crate::oracle::{EvaluationOracle, Product};
+use crate::parser::{parse_factors, parse_polynomial};
+use crate::protocol::run_protocol;
 use crate::sumcheck_pml::prover::*;
 use crate::sumcheck_pml::verifier::*;
 
 
+fn normal_poly<F: Field>(){
+    let rng = &mut test_rng();
+        
+    // (24*x_0 + 15*x_0*x_1 + 35*x_1) * (17*x_0 + 30*x_0*x_1 + 33*x_1)
+    let g = parse_factors::<F>("(24*x0 + 15*x0*x1 + 35*x1) * (17*x0 + 30*x0*x1 + 33*x1)").unwrap();
+
+    let prover = Prover::new(g.clone()).unwrap();
+    let outcome = run_protocol(prover, |claim| Verifier::with_options(Product::new(g.clone()).unwrap(), claim, VerifierOptions::default().allow_insecure()).unwrap(), rng).unwrap();
+
+    assert!(outcome.accepted);
+    // every round polynomial is the product of two linear ones
+    assert!(outcome.transcript.rounds.iter().all(|p| p.degree() <= 2));
+}
+
 #[test]
 fn test_normal_poly(){
-    #[derive(MontConfig)]
-    #[modulus = "97"]
-    #[generator = "5"]
-    struct FrConfig;
+    for_each_field!(F => normal_poly::<F>());
+}
 
-    type Fp97 = Fp64<MontBackend<FrConfig, 1>>;
+#[test]
+fn test_malformed_input(){
+    use crate::error::Error;
+    use crate::fields::Fp97;
 
     let rng = &mut test_rng();
-        
-    let g = vec![
-        // 24 * x_0   +   15 * x_0 * x_1   +   35 * x_1
-        multivariate::SparsePolynomial::from_coefficients_slice( 2 ,
-            &[
-                (Fp97::from_bigint(24u32.into()).unwrap(),multivariate::SparseTerm::new(vec![(0, 1)]),),
-                (Fp97::from_bigint(15u32.into()).unwrap(),multivariate::SparseTerm::new(vec![(0, 1), (1, 1)]),),
-                (Fp97::from_bigint(35u32.into()).unwrap(),multivariate::SparseTerm::new(vec![(1, 1)]),),
-            ]),
-        // 17*x0 + 30*x0*x1 + 33*x1,
-        multivariate::SparsePolynomial::from_coefficients_slice( 2 ,
-            &[
-                (Fp97::from_bigint(17u32.into()).unwrap(),multivariate::SparseTerm::new(vec![(0, 1)]),),
-                (Fp97::from_bigint(30u32.into()).unwrap(),multivariate::SparseTerm::new(vec![(0, 1), (1, 1)]),),
-                (Fp97::from_bigint(33u32.into()).unwrap(),multivariate::SparseTerm::new(vec![(1, 1)]),),
-            ]),
-    ];
-    println!("=============== Given Polynomial ===================");
-    println!("{:?}",g.clone());
-    println!("==========================================================");
-
-    let mut prover = Prover::new(g.clone());
-    let mut verifier = Verifier::new(Some(g.clone()),prover.claim());
-
-    println!("prover.claim => {:?}",prover.claim());
-
-    let mut r_j = Fp97::one();
-
-    for j in 0..prover.num_vars() {
-        let p = prover.round(r_j, j);
-        println!("Round-{} p => {:?}",j,&p);
-        let verifier_res = verifier.round(p, rng).unwrap();
-        match verifier_res {
-            VerifierRoundResult::JthRound(r) => {
-                println!("Round-{} V's r => {:?} expect => {:?}",j,&r,verifier.expect);
-                r_j = r;
-            }
-            VerifierRoundResult::FinalRound(res) => {
-                assert!(res);
-                break;
+    let g = parse_factors::<Fp97>("(24*x0 + 15*x0*x1 + 35*x1) * (17*x0 + 30*x0*x1 + 33*x1)").unwrap();
+
+    assert!(matches!(Prover::new(Vec::<multivariate::SparsePolynomial<Fp97, SparseTerm>>::new()), Err(Error::EmptyInput)));
+    let mismatched = vec![parse_polynomial::<Fp97>("x0").unwrap(), parse_polynomial::<Fp97>("x1 + x2").unwrap()];
+    assert!(matches!(Prover::new(mismatched), Err(Error::NumVarsMismatch(1, 3))));
+
+    // 97 linear factors need 98 evaluation points, more than there are elements
+    let many = vec![parse_polynomial::<Fp97>("x0 + 1").unwrap(); 97];
+    assert!(matches!(Prover::new(many), Err(Error::FieldTooSmall(98))));
+    type V = Verifier<Fp97, Product<multivariate::SparsePolynomial<Fp97, SparseTerm>>>;
+    assert!(matches!(V::univariate_interpolate_and_evaluate(&[Fp97::one(); 98], Fp97::one()), Err(Error::FieldTooSmall(98))));
+    assert_eq!(V::univariate_interpolate_and_evaluate(&[Fp97::one(), Fp97::from(3u8)], Fp97::from(5u8)), Ok(Fp97::from(11u8)));
+
+    let mut prover = Prover::new(g.clone()).unwrap();
+    let mut verifier = Verifier::with_options(Product::new(g).unwrap(), prover.claim(), VerifierOptions::default().allow_insecure()).unwrap();
+    let p = prover.round(Fp97::one(), 0).unwrap();
+    assert!(matches!(verifier.round(p[..1].to_vec(), rng), Err(Error::WrongMessageLength(0, 1, 3))));
+
+    let mut wrong = p.clone();
+    wrong[0] += Fp97::one();
+    let expected = p[0] + p[1];
+    assert_eq!(verifier.round(wrong, rng).unwrap_err(), Error::ClaimMismatch(0, expected, expected + Fp97::one()));
+
+    let VerifierRoundResult::JthRound(r) = verifier.round(p, rng).unwrap() else { panic!("expected a challenge") };
+    let p = prover.round(r, 1).unwrap();
+    assert!(matches!(verifier.round(p.clone(), rng), Ok(VerifierRoundResult::FinalRound(true, _))));
+    assert!(matches!(verifier.round(p, rng), Err(Error::RoundOutOfOrder(2, 2))));
+    assert!(matches!(verifier.expected_len(), Err(Error::RoundOutOfOrder(2, 2))));
+
+    // an oracle whose degrees do not cover every variable is refused up front
+    struct NoDegrees;
+    impl EvaluationOracle<Fp97> for NoDegrees {
+        fn num_vars(&self) -> usize { 2 }
+        fn degrees(&self) -> Vec<usize> { vec![] }
+        fn query(&mut self, _: &[Fp97]) -> Result<Fp97, Error<Fp97>> { Ok(Fp97::one()) }
+    }
+    let options = VerifierOptions::default().allow_insecure();
+    assert!(matches!(Verifier::with_options(NoDegrees, Fp97::one(), options), Err(Error::NumVarsMismatch(2, 0))));
+    assert!(matches!(crate::sumcheck_pml::verify(&crate::fiat_shamir::Proof { messages: vec![] }, Fp97::one(), &mut NoDegrees, crate::soundness::SecurityConfig::default()), Err(Error::NumVarsMismatch(2, 0))));
+}
+
+#[test]
+fn test_public_claim(){
+    use crate::error::Error;
+    use crate::fields::Fp97;
+    use crate::protocol::run_protocol_on_claim;
+
+    let rng = &mut test_rng();
+    let g = parse_factors::<Fp97>("(x0 + 2*x1) * (3*x0*x1 + 5)").unwrap();
+    // 0*5 + 1*5 + 2*5 + 3*8 over the four points
+    let public = Fp97::from(39u8);
+    let verifier = |claim| Verifier::with_options(Product::new(g.clone()).unwrap(), claim, VerifierOptions::default().allow_insecure()).unwrap();
+
+    let prover = Prover::new(g.clone()).unwrap().for_claim(public).unwrap();
+    assert!(run_protocol_on_claim(prover, public, verifier, rng).unwrap().accepted);
+
+    let false_claim = public + Fp97::one();
+    assert!(matches!(Prover::new(g.clone()).unwrap().for_claim(false_claim), Err(Error::FalseClaim(c, s)) if c == false_claim && s == public));
+    let res = run_protocol_on_claim(Prover::new(g.clone()).unwrap(), false_claim, verifier, rng);
+    assert_eq!(res.unwrap_err(), Error::ClaimMismatch(0, false_claim, public));
+}
+
+#[test]
+fn test_binding_order(){
+    use crate::fields::Fp97;
+    use crate::poly::{BindingOrder, OracleEvaluation};
+
+    let rng = &mut test_rng();
+    let g = parse_factors::<Fp97>("(24*x0 + 15*x0*x1 + 35*x2) * (17*x0 + 30*x1*x2 + 33*x1)").unwrap();
+    for order in [BindingOrder::LowToHigh, BindingOrder::HighToLow] {
+        let prover = Prover::with_binding_order(g.clone(), order).unwrap();
+        let verifier = |claim| Verifier::with_options(Product::new(g.clone()).unwrap(), claim, VerifierOptions::default().allow_insecure().binding_order(order)).unwrap();
+        assert!(run_protocol(prover, verifier, rng).unwrap().accepted);
+    }
+
+    // the same challenges, reversed, bind the same point in either order
+    let r = [Fp97::from(3u8), Fp97::from(10u8), Fp97::from(42u8)];
+    let expected: Fp97 = g.iter().map(|f| OracleEvaluation::evaluate(f, &r).unwrap()).product();
+    for (order, challenges) in [(BindingOrder::LowToHigh, r), (BindingOrder::HighToLow, [r[2], r[1], r[0]])] {
+        let mut prover = Prover::with_binding_order(g.clone(), order).unwrap();
+        let mut value = prover.claim();
+        let mut r_prev = Fp97::one();
+        for (j, r_j) in challenges.iter().enumerate() {
+            let p = prover.round(r_prev, j).unwrap();
+            assert_eq!(value, p[0] + p[1]);
+            value = crate::round_poly::interpolate_at(&p, *r_j);
+            r_prev = *r_j;
+        }
+        assert_eq!(value, expected);
+    }
+}
+
+#[test]
+fn test_per_variable_degrees(){
+    use crate::error::Error;
+    use crate::fields::Fp97;
+    use crate::poly::BindingOrder;
+
+    let rng = &mut test_rng();
+    // the product has degree 3 in $x_0$, 2 in $x_1$ and 1 in $x_2$
+    let g = parse_factors::<Fp97>("(x0^3 + 3*x1 + x2) * (5*x1 + 2)").unwrap();
+    let expanded = parse_polynomial::<Fp97>("5*x0^3*x1 + 2*x0^3 + 15*x1^2 + 6*x1 + 5*x1*x2 + 2*x2").unwrap();
+    let sum = crate::poly::HypercubeEvaluation::to_evaluations(&expanded).into_iter().sum::<Fp97>();
+
+    for (order, lens) in [(BindingOrder::LowToHigh, [4, 3, 2]), (BindingOrder::HighToLow, [2, 3, 4])] {
+        let mut prover = Prover::with_binding_order(g.clone(), order).unwrap();
+        assert_eq!(prover.claim(), sum);
+        let mut verifier = Verifier::with_options(Product::new(g.clone()).unwrap(), sum, VerifierOptions::default().allow_insecure().binding_order(order)).unwrap();
+        let mut r = Fp97::one();
+        for (j, len) in lens.into_iter().enumerate() {
+            let p = prover.round(r, j).unwrap();
+            assert_eq!(p.len(), len);
+            assert_eq!(verifier.expected_len(), Ok(len));
+            match verifier.round(p, rng).unwrap() {
+                VerifierRoundResult::JthRound(r_j) => r = r_j,
+                VerifierRoundResult::FinalRound(res, _) => assert!(res && j == 2),
             }
         }
     }
 
+    // the bound of every round is enforced, padding included
+    let mut prover = Prover::new(g.clone()).unwrap();
+    let mut verifier = Verifier::with_options(Product::new(g).unwrap(), sum, VerifierOptions::default().allow_insecure()).unwrap();
+    let mut p = prover.round(Fp97::one(), 0).unwrap();
+    p.push(crate::round_poly::interpolate_at(&p, Fp97::from(4u8)));
+    assert!(matches!(verifier.round(p, rng), Err(Error::WrongMessageLength(0, 5, 4))));
+
+    // a factor not depending on a variable leaves a constant round polynomial
+    let g = vec![parse_polynomial::<Fp97>("3*x1 + 1").unwrap()];
+    let prover = Prover::new(g.clone()).unwrap();
+    let outcome = run_protocol(prover, |claim| Verifier::with_options(Product::new(g.clone()).unwrap(), claim, VerifierOptions::default().allow_insecure()).unwrap(), rng).unwrap();
+    assert!(outcome.accepted);
+    assert_eq!(outcome.transcript.rounds[0].degree(), 0);
+}
+
+#[test]
+fn test_partial(){
+    use crate::error::Error;
+    use crate::fields::Fp97;
+    use crate::poly::PartialEvaluation;
+
+    let rng = &mut test_rng();
+    let g = parse_factors::<Fp97>("(x0 + 2*x1*x2) * (3*x0^2 + x2 + x3) * (x1 + x3^2)").unwrap();
+
+    let mut prover = Prover::new(g.clone()).unwrap().partial(2).unwrap();
+    let mut verifier = Verifier::with_options(Product::new(g.clone()).unwrap(), prover.claim(), VerifierOptions::default().allow_insecure()).unwrap().partial(2).unwrap();
+    let mut r = Fp97::one();
+    for j in 0..prover.num_rounds() {
+        match verifier.round(prover.round(r, j).unwrap(), rng).unwrap() {
+            VerifierRoundResult::JthRound(r_j) => r = r_j,
+            VerifierRoundResult::FinalRound(..) => unreachable!(),
+        }
+    }
+    assert!(matches!(prover.clone().round(r, 2), Err(Error::RoundOutOfOrder(2, 2))));
+    assert!(matches!(Prover::new(g.clone()).unwrap().into_residual(r), Err(Error::InvalidRoundCount(1, 4))));
+    let residual = prover.into_residual(r).unwrap();
+    assert_eq!(residual.claim, verifier.expect);
+    assert_eq!(residual.num_vars(), 2);
+    assert_eq!(residual.tables().len(), 3);
+
+    // the next stage proves the residual claim about the fixed factors
+    let rest: Vec<_> = g.iter().map(|f| f.fix_variables(verifier.challenges())).collect();
+    let claim = residual.claim;
+    let prover = Prover::resume(residual).unwrap();
+    let outcome = run_protocol(prover, |_| Verifier::with_options(Product::new(rest.clone()).unwrap(), claim, VerifierOptions::default().allow_insecure()).unwrap(), rng).unwrap();
+    assert!(outcome.accepted);
+
+    // the same partial run driven by run_protocol
+    let mut prover = Prover::new(g.clone()).unwrap().partial(2).unwrap();
+    let verifier = |claim| Verifier::with_options(Product::new(g.clone()).unwrap(), claim, VerifierOptions::default().allow_insecure()).unwrap().partial(2).unwrap();
+    let outcome = run_protocol(&mut prover, verifier, rng).unwrap();
+    assert!(outcome.accepted);
+    assert_eq!(outcome.transcript.challenges.len(), 2);
+    let r_last = outcome.transcript.challenges[1];
+    let residual = prover.into_residual(r_last).unwrap();
+    assert_eq!(residual.claim, outcome.transcript.rounds[1].evaluate(r_last));
+    assert_eq!(residual.num_vars(), 2);
+}
+
+#[test]
+fn test_finalize(){
+    use crate::error::Error;
+    use crate::fields::Fp97;
+    use crate::poly::{BindingOrder, OracleEvaluation};
+
+    let rng = &mut test_rng();
+    let g = parse_factors::<Fp97>("(x0 + 2*x1*x2) * (3*x0^2 + x2) * (x1 + 5)").unwrap();
+
+    for order in [BindingOrder::LowToHigh, BindingOrder::HighToLow] {
+        let mut prover = Prover::with_binding_order(g.clone(), order).unwrap();
+        let mut verifier = Verifier::with_options(Product::new(g.clone()).unwrap(), prover.claim(), VerifierOptions::default().allow_insecure().binding_order(order)).unwrap();
+        let mut r = Fp97::one();
+        let mut accepted = false;
+        for j in 0..prover.num_vars() {
+            match verifier.round(prover.round(r, j).unwrap(), rng).unwrap() {
+                VerifierRoundResult::JthRound(r_j) => r = r_j,
+                VerifierRoundResult::FinalRound(res, r_last) => (accepted, r) = (res, r_last),
+            }
+        }
+        assert!(accepted);
+        // a partial run, or one stopped early, has no final point yet
+        let fresh = Prover::with_binding_order(g.clone(), order).unwrap();
+        assert!(matches!(fresh.clone().partial(2).unwrap().finalize(r), Err(Error::InvalidRoundCount(2, 3))));
+        assert!(matches!(fresh.finalize(r), Err(Error::InvalidRoundCount(1, 3))));
+
+        // the verifier's last challenge completes the point
+        let last = prover.finalize(r).unwrap();
+        assert_eq!(last.point, order.point(verifier.challenges()));
+        for (f, value) in g.iter().zip(&last.values) {
+            assert_eq!(f.evaluate(&last.point), Some(*value));
+        }
+    }
 }
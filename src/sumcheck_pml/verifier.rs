@@ -1,12 +1,18 @@
 use ark_ff::Field;
-use crate::sumcheck_pml::poly::*;
-use polynomial::Polynomial as IPoly;
+use crate::error::{check_evaluation_points, Error};
+use crate::oracle::EvaluationOracle;
+use crate::round_poly::{interpolate_at, RoundPolynomial};
+use crate::poly::{BindingOrder, RngF};
+use crate::sumcheck_pml::grid::round_degrees;
+use crate::soundness::{pml_soundness_bits, SecurityConfig};
 /// The state of the Verifier.
-pub struct Verifier<F: Field, P: SumCheckPolynomial<F>> {
+pub struct Verifier<F: Field, O: EvaluationOracle<F>> {
     nv: usize,// Number of variables in the original polynomial.
-    claim: F,// A $claim$ value claimed by the Prover.
+    rounds: usize,// Number of rounds to run, less than `nv` in a partial run.
     r: Vec<F>,// Previously picked random values $r_1,...,r_{j-1}$.
-    g: Option<Vec<P>>,// Original polynomial for oracle access
+    g: O,// Oracle access to the product of the factors
+    order: BindingOrder,// Order the prover binds the variables in.
+    degrees: Vec<usize>,// Degree bound $d_j$ of the round polynomial of every round.
     pub expect:F
 }
 
@@ -14,53 +20,153 @@ pub struct Verifier<F: Field, P: SumCheckPolynomial<F>> {
 #[derive(Debug)]
 pub enum VerifierRoundResult<F: Field> {
     JthRound(F),
-    FinalRound(bool),
+    /// The verdict and the challenge of the last round, which the prover needs for
+    /// [`crate::sumcheck_pml::prover::Prover::finalize`].
+    FinalRound(bool, F),
 }
 
-impl<F: Field, P: SumCheckPolynomial<F>> Verifier<F, P> {
-    /// Create the new state of the [`Verifier`].
-    /// $n$ - degree of the polynomial
+/// How a [`Verifier`] runs, see [`Verifier::with_options`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct VerifierOptions {
+    /// Order the prover binds the variables in.
+    pub order: BindingOrder,
+
+    /// Soundness the verifier refuses to run below.
+    pub security: SecurityConfig,
+}
+
+impl VerifierOptions {
+    /// For a prover binding the variables in the given order.
+    pub fn binding_order(mut self, order: BindingOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Refuses to run if the protocol does not reach the soundness required by `config`
+    /// instead of [`SecurityConfig::default`].
+    pub fn security(mut self, config: SecurityConfig) -> Self {
+        self.security = config;
+        self
+    }
+
+    /// Runs even below the required soundness, see [`SecurityConfig::allow_insecure`].
+    pub fn allow_insecure(mut self) -> Self {
+        self.security = self.security.allow_insecure();
+        self
+    }
+}
+
+impl<F: Field, O: EvaluationOracle<F>> Verifier<F, O> {
+    /// Create the new state of the [`Verifier`], which refuses to run below the soundness
+    /// [`SecurityConfig::default`] requires.
     /// $claim$ - the value claimed to be true answer by the [`Prover`].
-    pub fn new(g: Option<Vec<P>>,claim: F) -> Self {
-		let num_vars = g.as_ref().unwrap()[0].num_vars();
-        Self {
+    /// $g$ - oracle access to the product of the factors for the [`Verifier`], e.g. the
+    /// factors themselves as a [`crate::oracle::Product`].
+    pub fn new(g: O,claim: F) -> Result<Self, Error<F>> {
+        Self::with_options(g, claim, VerifierOptions::default())
+    }
+
+    /// Same as [`Verifier::new`], running as `options` say.
+    pub fn with_options(g: O, claim: F, options: VerifierOptions) -> Result<Self, Error<F>> {
+		let degrees = oracle_degree_bounds(&g, &options)?;
+        let num_vars = degrees.len();
+        Ok(Self {
             nv:num_vars,
-            claim,
+            rounds: num_vars,
             r: Vec::with_capacity(num_vars),
             g,
+            order: options.order,
+            degrees,
             expect:claim
+        })
+    }
+
+    /// Returns the number of variables, which is also the number of rounds.
+    pub fn num_vars(&self) -> usize {
+        self.nv
+    }
+
+    /// Evaluates at `eval_at` the polynomial through $(t, ys[t])$, $t = 0,1,...$ Fails if the
+    /// characteristic of `F` is too small for the points to be distinct.
+    pub fn univariate_interpolate_and_evaluate(ys:&[F],eval_at:F) -> Result<F, Error<F>> {
+        check_evaluation_points(ys.len())?;
+        Ok(interpolate_at(ys, eval_at))
+    }
+
+    /// Returns the number of evaluations $d_j + 1$ the next round message must have. Fails
+    /// with [`Error::RoundOutOfOrder`] once every round has been played.
+    pub fn expected_len(&self) -> Result<usize, Error<F>> {
+        let j = self.r.len();
+        if j >= self.rounds {
+            return Err(Error::RoundOutOfOrder(j, self.rounds));
+        }
+        Ok(self.degrees[j] + 1)
+    }
+
+    /// Runs only the first `rounds` rounds. The last of them returns a challenge instead of
+    /// checking the factors, and leaves [`Verifier::expect`] as the claimed sum of their
+    /// product over the remaining variables with the bound ones fixed to
+    /// [`Verifier::challenges`]. The next stage checks that claim, e.g. with a new
+    /// [`Verifier`] for the fixed factors.
+    pub fn partial(mut self, rounds: usize) -> Result<Self, Error<F>> {
+        if rounds == 0 || rounds > self.nv {
+            return Err(Error::InvalidRoundCount(rounds, self.nv));
         }
+        self.rounds = rounds;
+        Ok(self)
     }
 
-    pub fn univariate_interpolate_and_evaluate(ys:&Vec<F>,eval_at:F) -> F {
-        let xs:Vec<F> = (0..ys.len()).map(|x| F::from(x as u32)).collect();
-        let poly = IPoly::lagrange(&xs,&ys).unwrap();
-        poly.eval(eval_at)
+    /// Returns the oracle, e.g. to read what a [`crate::oracle::CountingOracle`] counted.
+    pub fn oracle(&self) -> &O {
+        &self.g
+    }
+
+    /// Returns the challenges drawn so far, in the order of the rounds.
+    pub fn challenges(&self) -> &[F] {
+        &self.r
     }
 
     /// Perform the $j$-th round of the [`Verifier`] side of the protocol.
-    pub fn round<R: RngF<F>>(&mut self,p:Vec<F>,rng: &mut R) -> Result<VerifierRoundResult<F>, Error> {
+    pub fn round<R: RngF<F>>(&mut self,p:Vec<F>,rng: &mut R) -> Result<VerifierRoundResult<F>, Error<F>> {
 		let r_j = rng.draw();
 
-        if self.expect != p[0] + p[1] {
-            Err(Error::ProverClaimMismatch(
-                format!("{:?}", self.expect),
-                format!("{:?} {:?}", p[0] ,p[1]),
-            ))
+        let j = self.r.len();
+        let num_evals = self.expected_len()?;
+        if p.len() != num_evals {
+            return Err(Error::WrongMessageLength(j, p.len(), num_evals));
+        }
+        // a constant round polynomial is sent as its single value
+        let g_j = RoundPolynomial::from_evaluations(p)?;
+        if g_j.sum_over_boolean() != self.expect {
+            Err(Error::ClaimMismatch(j, self.expect, g_j.sum_over_boolean()))
         } else if self.r.len() == (self.nv - 1) {
             // Last round
             self.r.push(r_j);
-            if let Some(g) = &self.g {
-                Ok(VerifierRoundResult::FinalRound(Self::univariate_interpolate_and_evaluate(&p,r_j) == 
-                                            g.iter().map(|f| f.evaluate(&self.r).unwrap()).product()
-                                        ))
-            } else {
-                Err(Error::NoPolySet)
-            }
+            let oracle = self.g.query(&self.order.point(&self.r))?;
+            Ok(VerifierRoundResult::FinalRound(g_j.evaluate(r_j) == oracle, r_j))
         } else {
             self.r.push(r_j);
-            self.expect = Self::univariate_interpolate_and_evaluate(&p,r_j);
+            self.expect = g_j.evaluate(r_j);
             Ok(VerifierRoundResult::JthRound(r_j))
         }
     }
 }
+
+/// Checks that the product `g` can be verified as `options` say, at the soundness they
+/// require, and returns the degree bound of every round, in the order of the rounds. [`Verifier::with_options`] and
+/// [`crate::sumcheck_pml::verify`] both start here.
+pub(crate) fn oracle_degree_bounds<F: Field, O: EvaluationOracle<F>>(g: &O, options: &VerifierOptions) -> Result<Vec<usize>, Error<F>> {
+    let num_vars = g.num_vars();
+    if num_vars == 0 {
+        return Err(Error::EmptyInput);
+    }
+    let degrees = g.degrees();
+    if degrees.len() != num_vars {
+        return Err(Error::NumVarsMismatch(num_vars, degrees.len()));
+    }
+    let degrees = round_degrees(&degrees, options.order);
+    check_evaluation_points(degrees.iter().max().map_or(1, |d| d + 1))?;
+    // the round degrees of the product are its degrees in the variables
+    options.security.check(pml_soundness_bits::<F>(&degrees))?;
+    Ok(degrees)
+}
@@ -0,0 +1,154 @@
+//! Differential tests running the ml, pml and naive provers on equivalent inputs.
+use ark_ff::Field;
+use ark_poly::{
+    multivariate::{SparsePolynomial, SparseTerm, Term},
+    univariate, DenseMVPolynomial, Polynomial,
+};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use crate::error::Error;
+use crate::oracle::Product;
+use crate::poly::OracleEvaluation;
+use crate::protocol::{run_protocol, SumcheckProver, SumcheckVerifier};
+use crate::round_poly::RoundPolynomial;
+use crate::{sumcheck_ml, sumcheck_naive, sumcheck_pml};
+
+type Poly<F> = SparsePolynomial<F, SparseTerm>;
+
+/// The multilinear polynomial whose evaluation at the point with bits `b` is `table[b]`,
+/// with bit $i$ of the index being $x_i$.
+fn multilinear_from_table<F: Field>(num_vars: usize, table: &[u64]) -> Poly<F> {
+    let mut coeffs: Vec<F> = table.iter().map(|v| F::from(*v)).collect();
+    // Möbius inversion over the subset lattice
+    for i in 0..num_vars {
+        for mask in 0..coeffs.len() {
+            if mask & (1 << i) != 0 {
+                let lower = coeffs[mask ^ (1 << i)];
+                coeffs[mask] -= lower;
+            }
+        }
+    }
+
+    let terms = coeffs
+        .into_iter()
+        .enumerate()
+        .map(|(mask, c)| (c, SparseTerm::new((0..num_vars).filter(|i| mask & (1 << i) != 0).map(|i| (i, 1)).collect())))
+        .collect();
+    SparsePolynomial::from_coefficients_vec(num_vars, terms)
+}
+
+fn mul<F: Field>(a: &Poly<F>, b: &Poly<F>) -> Poly<F> {
+    let mut terms = Vec::new();
+    for (ca, ta) in a.terms() {
+        for (cb, tb) in b.terms() {
+            let mut vars = ta.to_vec();
+            vars.extend(tb.to_vec());
+            terms.push((*ca * cb, SparseTerm::new(vars)));
+        }
+    }
+    SparsePolynomial::from_coefficients_vec(a.num_vars, terms)
+}
+
+fn eval_naive<F: Field>(g_j: &univariate::SparsePolynomial<F>, t: usize) -> F {
+    g_j.evaluate(&F::from(t as u64))
+}
+
+/// Runs an honest verifier against `prover` with challenges drawn from `seed`.
+fn accepts<F, P, V>(prover: P, verifier: V, seed: u64) -> bool
+where
+    F: Field,
+    P: SumcheckProver<F>,
+    V: SumcheckVerifier<F, Message = P::Message, Error = Error<F>>,
+{
+    let rng = &mut StdRng::seed_from_u64(seed);
+    run_protocol(prover, |_| verifier, rng).is_ok_and(|outcome| outcome.accepted)
+}
+
+/// ml, pml on a single factor and naive agree on one multilinear polynomial.
+fn check_single<F: Field>(num_vars: usize, table: &[u64], challenges: &[u64], seed: u64) {
+    let g = multilinear_from_table::<F>(num_vars, table);
+    let mut ml = sumcheck_ml::prover::Prover::new(g.clone()).unwrap();
+    let mut pml = sumcheck_pml::prover::Prover::new(vec![g.clone()]).unwrap();
+    let mut naive = sumcheck_naive::Prover::new(g.clone()).unwrap();
+
+    let sum: F = table.iter().map(|v| F::from(*v)).sum();
+    assert_eq!(ml.claim(), sum);
+    assert_eq!(pml.claim(), sum);
+    assert_eq!(naive.c_1(), sum);
+
+    let mut r_j = F::one();
+    for (j, r) in challenges.iter().enumerate() {
+        let (p0, p1) = ml.round(r_j, j).unwrap();
+        let p = pml.round(r_j, j).unwrap();
+        let g_j = naive.round(r_j, j).unwrap();
+        // pml sends a single value for a variable $g$ does not depend on
+        assert_eq!(RoundPolynomial::from_evaluations(p).unwrap(), RoundPolynomial::from((p0, p1)));
+        assert_eq!((eval_naive(&g_j, 0), eval_naive(&g_j, 1)), (p0, p1));
+        r_j = F::from(*r);
+    }
+
+    let claim = sum;
+    assert!(accepts(sumcheck_ml::prover::Prover::new(g.clone()).unwrap(), sumcheck_ml::verifier::Verifier::with_options(g.clone(), claim, sumcheck_ml::verifier::VerifierOptions::default().allow_insecure()).unwrap(), seed));
+    assert!(accepts(sumcheck_pml::prover::Prover::new(vec![g.clone()]).unwrap(), sumcheck_pml::verifier::Verifier::with_options(Product::new(vec![g.clone()]).unwrap(), claim, sumcheck_pml::verifier::VerifierOptions::default().allow_insecure()).unwrap(), seed));
+    let mut verifier = sumcheck_naive::Verifier::with_options(g.clone(), sumcheck_naive::VerifierOptions::default().allow_insecure()).unwrap();
+    verifier.set_c_1(claim);
+    assert!(accepts(sumcheck_naive::Prover::new(g).unwrap(), verifier, seed));
+}
+
+/// pml on a list of factors agrees with naive on their expanded product.
+fn check_product<F: Field>(num_vars: usize, tables: &[Vec<u64>], challenges: &[u64], seed: u64) {
+    let factors: Vec<Poly<F>> = tables.iter().map(|t| multilinear_from_table(num_vars, t)).collect();
+    let product = factors[1..].iter().fold(factors[0].clone(), |acc, f| mul(&acc, f));
+
+    let mut pml = sumcheck_pml::prover::Prover::new(factors.clone()).unwrap();
+    let mut naive = sumcheck_naive::Prover::new(product.clone()).unwrap();
+
+    let sum: F = (0..1usize << num_vars)
+        .map(|b| tables.iter().map(|t| F::from(t[b])).product::<F>())
+        .sum();
+    assert_eq!(pml.claim(), sum);
+    assert_eq!(naive.c_1(), sum);
+
+    let mut r_j = F::one();
+    for (j, r) in challenges.iter().enumerate() {
+        let p = pml.round(r_j, j).unwrap();
+        let g_j = naive.round(r_j, j).unwrap();
+        let degree: usize = factors.iter().map(|f| OracleEvaluation::degrees(f)[j]).sum();
+        assert_eq!(p.len(), degree + 1);
+        for (t, p_t) in p.iter().enumerate() {
+            assert_eq!(eval_naive(&g_j, t), *p_t);
+        }
+        r_j = F::from(*r);
+    }
+
+    assert!(accepts(sumcheck_pml::prover::Prover::new(factors.clone()).unwrap(), sumcheck_pml::verifier::Verifier::with_options(Product::new(factors).unwrap(), sum, sumcheck_pml::verifier::VerifierOptions::default().allow_insecure()).unwrap(), seed));
+    let mut verifier = sumcheck_naive::Verifier::with_options(product.clone(), sumcheck_naive::VerifierOptions::default().allow_insecure()).unwrap();
+    verifier.set_c_1(sum);
+    assert!(accepts(sumcheck_naive::Prover::new(product).unwrap(), verifier, seed));
+}
+
+fn single_input() -> impl Strategy<Value = (usize, Vec<u64>, Vec<u64>, u64)> {
+    (1usize..=5).prop_flat_map(|n| (Just(n), vec(any::<u64>(), 1 << n), vec(any::<u64>(), n), any::<u64>()))
+}
+
+fn product_input() -> impl Strategy<Value = (usize, Vec<Vec<u64>>, Vec<u64>, u64)> {
+    (1usize..=4, 1usize..=3).prop_flat_map(|(n, k)| {
+        (Just(n), vec(vec(any::<u64>(), 1 << n), k), vec(any::<u64>(), n), any::<u64>())
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn test_single((n, table, challenges, seed) in single_input()) {
+        for_each_field!(F => check_single::<F>(n, &table, &challenges, seed));
+    }
+
+    #[test]
+    fn test_product((n, tables, challenges, seed) in product_input()) {
+        for_each_field!(F => check_product::<F>(n, &tables, &challenges, seed));
+    }
+}
@@ -0,0 +1,187 @@
+use std::time::Duration;
+
+use ark_ff::Field;
+
+use crate::transport::{Error, Transport};
+
+/// Default upper bound on the size of a single frame.
+pub const MAX_FRAME_LEN: usize = 1 << 24;
+
+/// The protocol a prover and verifier agree to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Ml,
+    Pml,
+    Naive,
+}
+
+/// Messages exchanged between a prover and a verifier process.
+///
+/// Round messages are sent as a list of field elements: $(p_0, p_1)$ for ml, the
+/// evaluations at $0,...,d$ for pml and the dense coefficients for naive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message<F: Field> {
+    Hello(Protocol, usize),
+    Claim(F),
+    Round(Vec<F>),
+    Challenge(F),
+    Verdict(bool),
+    Abort(String),
+}
+
+impl<F: Field> Message<F> {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Message::Hello(..) => "hello",
+            Message::Claim(_) => "claim",
+            Message::Round(_) => "round",
+            Message::Challenge(_) => "challenge",
+            Message::Verdict(_) => "verdict",
+            Message::Abort(_) => "abort",
+        }
+    }
+
+    fn tag(&self) -> u8 {
+        match self {
+            Message::Hello(..) => 0,
+            Message::Claim(_) => 1,
+            Message::Round(_) => 2,
+            Message::Challenge(_) => 3,
+            Message::Verdict(_) => 4,
+            Message::Abort(_) => 5,
+        }
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = vec![self.tag()];
+        match self {
+            Message::Hello(protocol, num_vars) => {
+                bytes.push(*protocol as u8);
+                let num_vars = u32::try_from(*num_vars).map_err(|_| Error::TooManyVars(*num_vars))?;
+                bytes.extend_from_slice(&num_vars.to_le_bytes());
+            }
+            Message::Claim(f) | Message::Challenge(f) => {
+                f.serialize_compressed(&mut bytes).unwrap();
+            }
+            Message::Round(values) => {
+                bytes.extend_from_slice(&(values.len() as u32).to_le_bytes());
+                for f in values {
+                    f.serialize_compressed(&mut bytes).unwrap();
+                }
+            }
+            Message::Verdict(accept) => bytes.push(*accept as u8),
+            Message::Abort(reason) => bytes.extend_from_slice(reason.as_bytes()),
+        }
+        Ok(bytes)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        let (&tag, mut body) = bytes.split_first().ok_or(Error::MalformedMessage(0))?;
+        let malformed = |_| Error::MalformedMessage(tag);
+
+        let message = match tag {
+            0 => {
+                let protocol = match body.first() {
+                    Some(0) => Protocol::Ml,
+                    Some(1) => Protocol::Pml,
+                    Some(2) => Protocol::Naive,
+                    _ => return Err(Error::MalformedMessage(tag)),
+                };
+                let num_vars = body.get(1..5).ok_or(Error::MalformedMessage(tag))?;
+                body = &body[5..];
+                Message::Hello(protocol, u32::from_le_bytes(num_vars.try_into().unwrap()) as usize)
+            }
+            1 => Message::Claim(F::deserialize_compressed(&mut body).map_err(malformed)?),
+            2 => {
+                let len = body.get(..4).ok_or(Error::MalformedMessage(tag))?;
+                let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+                body = &body[4..];
+                // every element takes at least one byte, which bounds the allocation below
+                if len > body.len() {
+                    return Err(Error::MalformedMessage(tag));
+                }
+                let mut values = Vec::with_capacity(len);
+                for _ in 0..len {
+                    values.push(F::deserialize_compressed(&mut body).map_err(malformed)?);
+                }
+                Message::Round(values)
+            }
+            3 => Message::Challenge(F::deserialize_compressed(&mut body).map_err(malformed)?),
+            4 => {
+                let accept = match body.first() {
+                    Some(0) => false,
+                    Some(1) => true,
+                    _ => return Err(Error::MalformedMessage(tag)),
+                };
+                body = &body[1..];
+                Message::Verdict(accept)
+            }
+            5 => {
+                let reason = String::from_utf8_lossy(body).into_owned();
+                body = &[];
+                Message::Abort(reason)
+            }
+            _ => return Err(Error::UnknownTag(tag)),
+        };
+
+        if body.is_empty() {
+            Ok(message)
+        } else {
+            Err(Error::MalformedMessage(tag))
+        }
+    }
+}
+
+/// Length-prefixed message framing on top of a [`Transport`].
+///
+/// Every frame is a little-endian `u32` length followed by a one-byte tag and the
+/// message body.
+pub struct Channel<T: Transport> {
+    transport: T,
+    max_frame_len: usize,
+}
+
+impl<T: Transport> Channel<T> {
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            max_frame_len: MAX_FRAME_LEN,
+        }
+    }
+
+    /// Rejects incoming frames longer than `max_frame_len` bytes.
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    /// Sets how long [`Channel::recv`] waits for the peer.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) -> Result<(), Error> {
+        self.transport.set_timeout(timeout)
+    }
+
+    pub fn send<F: Field>(&mut self, message: &Message<F>) -> Result<(), Error> {
+        let body = message.encode()?;
+        let mut frame = Vec::with_capacity(4 + body.len());
+        frame.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        frame.extend_from_slice(&body);
+        self.transport.send(&frame)
+    }
+
+    pub fn recv<F: Field>(&mut self) -> Result<Message<F>, Error> {
+        let mut len = [0u8; 4];
+        self.transport.recv_exact(&mut len)?;
+        let len = u32::from_le_bytes(len) as usize;
+        if len > self.max_frame_len {
+            return Err(Error::FrameTooLarge(len, self.max_frame_len));
+        }
+
+        let mut body = vec![0u8; len];
+        self.transport.recv_exact(&mut body)?;
+        Message::decode(&body)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.transport
+    }
+}
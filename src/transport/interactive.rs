@@ -0,0 +1,238 @@
+//! Drivers running the ml, pml and naive protocols between two processes.
+//!
+//! Both sides know the polynomial (the verifier needs oracle access to it), the prover
+//! opens with a `Hello` naming the protocol and the number of variables, followed by
+//! its claim. Each round message is answered with a challenge, the last one with the
+//! verdict. The pml verifier sends the challenge of the last round before its verdict, so
+//! the prover can [`finalize`](sumcheck_pml::prover::Prover::finalize) at the full point.
+//! A side that rejects a message, or a prover that cannot play a round, sends `Abort`
+//! before returning the error.
+use ark_ff::Field;
+use ark_std::rand::Rng;
+
+use crate::oracle::{EvaluationOracle, Product};
+use crate::poly::{HypercubeEvaluation, SumCheckPolynomial};
+use crate::round_poly::RoundPolynomial;
+use crate::sumcheck_ml;
+use crate::sumcheck_naive;
+use crate::sumcheck_pml;
+use crate::transport::channel::{Channel, Message, Protocol};
+use crate::transport::{Error, ProtocolError, Transport};
+
+enum Step<F> {
+    Challenge(F),
+    /// The verdict, preceded by the last challenge if the prover is told it.
+    Verdict(bool, Option<F>),
+}
+
+/// Runs the prover side of the ml protocol, returning the verifier's verdict.
+pub fn prove_ml<F, T>(channel: &mut Channel<T>, prover: &mut sumcheck_ml::prover::Prover<F>) -> Result<bool, ProtocolError<F>>
+where
+    F: Field,
+    T: Transport,
+{
+    let (accept, _) = prove(channel, Protocol::Ml, prover.num_vars(), prover.num_rounds(), prover.claim(), |r_j, j| {
+        let (p0, p1) = prover.round(r_j, j)?;
+        Ok(vec![p0, p1])
+    })?;
+    Ok(accept)
+}
+
+/// Runs the verifier side of the ml protocol with oracle access to `g`.
+pub fn verify_ml<F, O, T, R>(channel: &mut Channel<T>, g: O, options: sumcheck_ml::verifier::VerifierOptions, rng: &mut R) -> Result<bool, ProtocolError<F>>
+where
+    F: Field,
+    O: EvaluationOracle<F>,
+    T: Transport,
+    R: Rng,
+{
+    use sumcheck_ml::verifier::{Verifier, VerifierRoundResult};
+
+    let claim = handshake(channel, Protocol::Ml, g.num_vars())?;
+    let mut verifier = abort_on_error(channel, Verifier::with_options(g, claim, options).map_err(ProtocolError::from))?;
+    run_verifier(channel, |j, p| {
+        check_len(j, &p, 2)?;
+        Ok(match verifier.round(p[0], p[1], rng)? {
+            VerifierRoundResult::JthRound(r) => Step::Challenge(r),
+            VerifierRoundResult::FinalRound(res) => Step::Verdict(res, None),
+        })
+    })
+}
+
+/// Runs the prover side of the pml protocol, returning the verifier's verdict.
+pub fn prove_pml<F, T>(channel: &mut Channel<T>, prover: &mut sumcheck_pml::prover::Prover<F>) -> Result<bool, ProtocolError<F>>
+where
+    F: Field,
+    T: Transport,
+{
+    let (accept, _) = prove(channel, Protocol::Pml, prover.num_vars(), prover.num_rounds(), prover.claim(), |r_j, j| prover.round(r_j, j))?;
+    Ok(accept)
+}
+
+/// Same as [`prove_pml`], then binds the last variable to the verifier's last challenge and
+/// returns the verdict with the [`FinalClaim`](sumcheck_pml::prover::FinalClaim) of the run.
+pub fn prove_pml_and_finalize<F, T>(channel: &mut Channel<T>, mut prover: sumcheck_pml::prover::Prover<F>) -> Result<(bool, sumcheck_pml::prover::FinalClaim<F>), ProtocolError<F>>
+where
+    F: Field,
+    T: Transport,
+{
+    let (accept, r_last) = prove(channel, Protocol::Pml, prover.num_vars(), prover.num_rounds(), prover.claim(), |r_j, j| prover.round(r_j, j))?;
+    let r_last = r_last.ok_or(Error::UnexpectedMessage("challenge", "verdict"))?;
+    Ok((accept, prover.finalize(r_last)?))
+}
+
+/// Runs the verifier side of the pml protocol with oracle access to the factors `g`.
+pub fn verify_pml<F, P, T, R>(channel: &mut Channel<T>, g: Vec<P>, options: sumcheck_pml::verifier::VerifierOptions, rng: &mut R) -> Result<bool, ProtocolError<F>>
+where
+    F: Field,
+    P: HypercubeEvaluation<F>,
+    T: Transport,
+    R: Rng,
+{
+    use sumcheck_pml::verifier::{Verifier, VerifierRoundResult};
+
+    let claim = handshake(channel, Protocol::Pml, g.first().map_or(0, |f| f.num_vars()))?;
+    let verifier = Product::new(g).and_then(|g| Verifier::with_options(g, claim, options));
+    let mut verifier = abort_on_error(channel, verifier.map_err(ProtocolError::from))?;
+    run_verifier(channel, |j, p| {
+        check_len(j, &p, verifier.expected_len()?)?;
+        Ok(match verifier.round(p, rng)? {
+            VerifierRoundResult::JthRound(r) => Step::Challenge(r),
+            VerifierRoundResult::FinalRound(res, r_last) => Step::Verdict(res, Some(r_last)),
+        })
+    })
+}
+
+/// Runs the prover side of the naive protocol, returning the verifier's verdict.
+pub fn prove_naive<F, P, T>(channel: &mut Channel<T>, prover: &mut sumcheck_naive::Prover<F, P>) -> Result<bool, ProtocolError<F>>
+where
+    F: Field,
+    P: SumCheckPolynomial<F>,
+    T: Transport,
+{
+    let (accept, _) = prove(channel, Protocol::Naive, prover.num_vars(), prover.num_vars(), prover.c_1(), |r_j, j| {
+        Ok(RoundPolynomial::from(prover.round(r_j, j)?).to_coefficients())
+    })?;
+    Ok(accept)
+}
+
+/// Runs the verifier side of the naive protocol with oracle access to `g`.
+pub fn verify_naive<F, O, T, R>(channel: &mut Channel<T>, g: O, options: sumcheck_naive::VerifierOptions, rng: &mut R) -> Result<bool, ProtocolError<F>>
+where
+    F: Field,
+    O: EvaluationOracle<F>,
+    T: Transport,
+    R: Rng,
+{
+    use sumcheck_naive::{Verifier, VerifierRoundResult};
+
+    let claim = handshake(channel, Protocol::Naive, g.num_vars())?;
+    let verifier = Verifier::with_claim(g, claim, options);
+    let mut verifier = abort_on_error(channel, verifier.map_err(ProtocolError::from))?;
+    run_verifier(channel, |_, coeffs| {
+        Ok(match verifier.round(RoundPolynomial::from_coefficients(coeffs).into(), rng)? {
+            VerifierRoundResult::JthRound(r) => Step::Challenge(r),
+            VerifierRoundResult::FinalRound(res) => Step::Verdict(res, None),
+        })
+    })
+}
+
+/// Plays every one of the prover's `rounds`, returning the verdict and the last challenge
+/// if the verifier sent it. The verifiers on the other side run whole protocols, so a
+/// partial prover is refused before the handshake.
+fn prove<F: Field, T: Transport>(
+    channel: &mut Channel<T>,
+    protocol: Protocol,
+    num_vars: usize,
+    rounds: usize,
+    claim: F,
+    mut round: impl FnMut(F, usize) -> Result<Vec<F>, crate::error::Error<F>>,
+) -> Result<(bool, Option<F>), ProtocolError<F>> {
+    if rounds != num_vars {
+        return Err(crate::error::Error::InvalidRoundCount(rounds, num_vars).into());
+    }
+    channel.send(&Message::<F>::Hello(protocol, num_vars))?;
+    channel.send(&Message::Claim(claim))?;
+
+    let mut r_j = F::one();
+    for j in 0..rounds {
+        let message = abort_on_error(channel, round(r_j, j).map_err(ProtocolError::from))?;
+        channel.send(&Message::Round(message))?;
+        if j + 1 == rounds {
+            break;
+        }
+        match channel.recv()? {
+            Message::Challenge(r) => r_j = r,
+            Message::Abort(reason) => return Err(Error::Aborted(reason).into()),
+            m => return Err(Error::UnexpectedMessage("challenge", m.name()).into()),
+        }
+    }
+
+    let mut message = channel.recv::<F>()?;
+    let mut r_last = None;
+    if let Message::Challenge(r) = message {
+        r_last = Some(r);
+        message = channel.recv()?;
+    }
+    match message {
+        Message::Verdict(accept) => Ok((accept, r_last)),
+        Message::Abort(reason) => Err(Error::Aborted(reason).into()),
+        m => Err(Error::UnexpectedMessage("verdict", m.name()).into()),
+    }
+}
+
+fn handshake<F: Field, T: Transport>(channel: &mut Channel<T>, protocol: Protocol, num_vars: usize) -> Result<F, ProtocolError<F>> {
+    let result = match channel.recv::<F>()? {
+        Message::Hello(p, _) if p != protocol => Err(Error::ProtocolMismatch(protocol, p)),
+        Message::Hello(_, n) if n != num_vars => Err(Error::NumVarsMismatch(num_vars, n)),
+        Message::Hello(..) => match channel.recv()? {
+            Message::Claim(claim) => Ok(claim),
+            m => Err(Error::UnexpectedMessage("claim", m.name())),
+        },
+        m => Err(Error::UnexpectedMessage("hello", m.name())),
+    };
+    abort_on_error(channel, result.map_err(ProtocolError::from))
+}
+
+fn run_verifier<F: Field, T: Transport>(
+    channel: &mut Channel<T>,
+    mut round: impl FnMut(usize, Vec<F>) -> Result<Step<F>, ProtocolError<F>>,
+) -> Result<bool, ProtocolError<F>> {
+    let mut j = 0;
+    loop {
+        let step = match channel.recv()? {
+            Message::Round(p) => round(j, p),
+            Message::Abort(reason) => return Err(Error::Aborted(reason).into()),
+            m => Err(Error::UnexpectedMessage("round", m.name()).into()),
+        };
+
+        match abort_on_error(channel, step)? {
+            Step::Challenge(r) => channel.send(&Message::Challenge(r))?,
+            Step::Verdict(accept, r_last) => {
+                if let Some(r) = r_last {
+                    channel.send(&Message::Challenge(r))?;
+                }
+                channel.send(&Message::<F>::Verdict(accept))?;
+                return Ok(accept);
+            }
+        }
+        j += 1;
+    }
+}
+
+/// Tells the peer why the protocol stopped before handing the error back to the caller.
+fn abort_on_error<F: Field, T: Transport, V>(channel: &mut Channel<T>, result: Result<V, ProtocolError<F>>) -> Result<V, ProtocolError<F>> {
+    if let Err(e) = &result {
+        if !matches!(e, ProtocolError::Transport(Error::Timeout | Error::Disconnected)) {
+            let _ = channel.send(&Message::<F>::Abort(e.to_string()));
+        }
+    }
+    result
+}
+
+fn check_len<F>(j: usize, p: &[F], expected: usize) -> Result<(), Error> {
+    if p.len() != expected {
+        return Err(Error::InvalidRoundMessage(j, p.len(), expected));
+    }
+    Ok(())
+}
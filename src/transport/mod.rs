@@ -0,0 +1,215 @@
+pub mod channel;
+pub mod interactive;
+
+#[cfg(test)]
+mod test;
+
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ark_ff::Field;
+
+use crate::transport::channel::Protocol;
+
+/// An error type of the transport layer and of the messages exchanged over it.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("i/o error: {0}")]
+    Io(io::Error),
+
+    #[error("timed out waiting for the peer")]
+    Timeout,
+
+    #[error("peer disconnected")]
+    Disconnected,
+
+    #[error("frame of {0} bytes exceeds the limit of {1} bytes")]
+    FrameTooLarge(usize, usize),
+
+    #[error("unknown message tag {0}")]
+    UnknownTag(u8),
+
+    #[error("malformed message with tag {0}")]
+    MalformedMessage(u8),
+
+    #[error("expected {0} message, received {1}")]
+    UnexpectedMessage(&'static str, &'static str),
+
+    #[error("peer runs protocol {1:?}, expected {0:?}")]
+    ProtocolMismatch(Protocol, Protocol),
+
+    #[error("{0} variables do not fit into a hello message")]
+    TooManyVars(usize),
+
+    #[error("peer polynomial has {1} variables, expected {0}")]
+    NumVarsMismatch(usize, usize),
+
+    #[error("round {0} message has {1} values, expected {2}")]
+    InvalidRoundMessage(usize, usize, usize),
+
+    #[error("peer aborted the protocol: {0}")]
+    Aborted(String),
+}
+
+/// An error type of the protocols run over a [`Transport`], keeping the
+/// [`crate::error::Error`] of the prover or verifier so callers can match on it.
+#[derive(Debug, thiserror::Error)]
+pub enum ProtocolError<F: Field> {
+    #[error(transparent)]
+    Transport(#[from] Error),
+
+    #[error("sumcheck error: {0}")]
+    Sumcheck(#[from] crate::error::Error<F>),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => Error::Timeout,
+            io::ErrorKind::UnexpectedEof
+            | io::ErrorKind::BrokenPipe
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted => Error::Disconnected,
+            _ => Error::Io(e),
+        }
+    }
+}
+
+/// A reliable, ordered byte stream between a prover and a verifier.
+pub trait Transport {
+    /// Writes all of `bytes` to the peer.
+    fn send(&mut self, bytes: &[u8]) -> Result<(), Error>;
+
+    /// Reads exactly `buf.len()` bytes from the peer, failing with [`Error::Timeout`]
+    /// if they do not arrive within the configured timeout.
+    fn recv_exact(&mut self, buf: &mut [u8]) -> Result<(), Error>;
+
+    /// Sets the timeout for [`Transport::recv_exact`], `None` waits forever.
+    fn set_timeout(&mut self, timeout: Option<Duration>) -> Result<(), Error>;
+}
+
+/// A [`Transport`] over a TCP connection.
+pub struct TcpTransport {
+    stream: TcpStream,
+}
+
+impl TcpTransport {
+    pub fn new(stream: TcpStream) -> Result<Self, Error> {
+        stream.set_nodelay(true)?;
+        Ok(Self { stream })
+    }
+
+    /// Connects to a peer listening on `addr`.
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> Result<Self, Error> {
+        Self::new(TcpStream::connect(addr)?)
+    }
+
+    /// Waits for a peer to connect on `listener`.
+    pub fn accept(listener: &TcpListener) -> Result<Self, Error> {
+        let (stream, _) = listener.accept()?;
+        Self::new(stream)
+    }
+}
+
+impl Transport for TcpTransport {
+    fn send(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        self.stream.write_all(bytes)?;
+        Ok(self.stream.flush()?)
+    }
+
+    fn recv_exact(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        Ok(self.stream.read_exact(buf)?)
+    }
+
+    fn set_timeout(&mut self, timeout: Option<Duration>) -> Result<(), Error> {
+        Ok(self.stream.set_read_timeout(timeout)?)
+    }
+}
+
+/// A [`Transport`] over a pair of pipes, such as the stdin/stdout of this process or of a child.
+///
+/// Pipes have no native read timeout, so the reading end is drained by a background thread.
+pub struct PipeTransport<W: Write> {
+    rx: Receiver<io::Result<Vec<u8>>>,
+    buffer: VecDeque<u8>,
+    writer: W,
+    timeout: Option<Duration>,
+}
+
+impl<W: Write> PipeTransport<W> {
+    pub fn new<R: Read + Send + 'static>(mut reader: R, writer: W) -> Self {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            loop {
+                match reader.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        if tx.send(Ok(buf[..n].to_vec())).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => {
+                        let _ = tx.send(Err(e));
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self {
+            rx,
+            buffer: VecDeque::new(),
+            writer,
+            timeout: None,
+        }
+    }
+}
+
+impl PipeTransport<io::Stdout> {
+    /// Talks to the peer over the stdin and stdout of the current process.
+    pub fn stdio() -> Self {
+        Self::new(io::stdin(), io::stdout())
+    }
+}
+
+impl<W: Write> Transport for PipeTransport<W> {
+    fn send(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        self.writer.write_all(bytes)?;
+        Ok(self.writer.flush()?)
+    }
+
+    fn recv_exact(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        let deadline = self.timeout.map(|t| Instant::now() + t);
+
+        while self.buffer.len() < buf.len() {
+            let chunk = match deadline {
+                None => self.rx.recv().map_err(|_| Error::Disconnected)?,
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    self.rx.recv_timeout(remaining).map_err(|e| match e {
+                        RecvTimeoutError::Timeout => Error::Timeout,
+                        RecvTimeoutError::Disconnected => Error::Disconnected,
+                    })?
+                }
+            };
+            self.buffer.extend(chunk?);
+        }
+
+        let len = buf.len();
+        for (b, v) in buf.iter_mut().zip(self.buffer.drain(..len)) {
+            *b = v;
+        }
+        Ok(())
+    }
+
+    fn set_timeout(&mut self, timeout: Option<Duration>) -> Result<(), Error> {
+        self.timeout = timeout;
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use std::net::TcpListener;
+use std::thread;
+use std::time::Duration;
+
+use ark_ff::One;
+use ark_std::test_rng;
+
+use crate::fields::Fp97;
+use crate::parser::{parse_factors, parse_polynomial};
+use crate::poly::OracleEvaluation;
+use crate::transport::channel::{Channel, Message, Protocol};
+use crate::transport::interactive::*;
+use crate::transport::*;
+use crate::{sumcheck_ml, sumcheck_naive, sumcheck_pml};
+
+fn tcp_pair() -> (Channel<TcpTransport>, Channel<TcpTransport>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let client = thread::spawn(move || TcpTransport::connect(addr).unwrap());
+    let server = TcpTransport::accept(&listener).unwrap();
+    (Channel::new(client.join().unwrap()), Channel::new(server))
+}
+
+#[test]
+fn test_tcp_protocols(){
+    let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1 + 35*x1 + 3*x2").unwrap();
+    let (mut p_chan, mut v_chan) = tcp_pair();
+    let prover = {
+        let g = g.clone();
+        thread::spawn(move || prove_ml(&mut p_chan, &mut sumcheck_ml::prover::Prover::new(g).unwrap()))
+    };
+    assert!(verify_ml(&mut v_chan, g, sumcheck_ml::verifier::VerifierOptions::default().allow_insecure(), &mut test_rng()).unwrap());
+    assert!(prover.join().unwrap().unwrap());
+
+    let g = parse_factors::<Fp97>("(24*x0 + 15*x0*x1 + 35*x1) * (17*x0 + 30*x0*x1 + 33*x1)").unwrap();
+    let (mut p_chan, mut v_chan) = tcp_pair();
+    let prover = {
+        let g = g.clone();
+        thread::spawn(move || prove_pml(&mut p_chan, &mut sumcheck_pml::prover::Prover::new(g).unwrap()))
+    };
+    assert!(verify_pml(&mut v_chan, g, sumcheck_pml::verifier::VerifierOptions::default().allow_insecure(), &mut test_rng()).unwrap());
+    assert!(prover.join().unwrap().unwrap());
+
+    let g = parse_polynomial::<Fp97>("20*x0^2 + 5*x0^2*x1 + 29*x0*x1 + 62*x0^2*x1^2 + 90*x0*x1^2 + 88*x1^2").unwrap();
+    let (mut p_chan, mut v_chan) = tcp_pair();
+    let prover = {
+        let g = g.clone();
+        thread::spawn(move || prove_naive(&mut p_chan, &mut sumcheck_naive::Prover::new(g).unwrap()))
+    };
+    assert!(verify_naive(&mut v_chan, g, sumcheck_naive::VerifierOptions::default().allow_insecure(), &mut test_rng()).unwrap());
+    assert!(prover.join().unwrap().unwrap());
+}
+
+#[test]
+fn test_pipe_transport(){
+    let (v_reader, p_writer) = std::io::pipe().unwrap();
+    let (p_reader, v_writer) = std::io::pipe().unwrap();
+    let mut p_chan = Channel::new(PipeTransport::new(p_reader, p_writer));
+    let mut v_chan = Channel::new(PipeTransport::new(v_reader, v_writer));
+
+    let g = parse_factors::<Fp97>("(24*x0 + 15*x0*x1 + 35*x1) * (17*x0 + 30*x0*x1 + 33*x1)").unwrap();
+    let prover = {
+        let g = g.clone();
+        thread::spawn(move || prove_pml_and_finalize(&mut p_chan, sumcheck_pml::prover::Prover::new(g).unwrap()))
+    };
+    assert!(verify_pml(&mut v_chan, g.clone(), sumcheck_pml::verifier::VerifierOptions::default().allow_insecure(), &mut test_rng()).unwrap());
+    // the last challenge reached the prover, so it knows every factor at the final point
+    let (accept, last) = prover.join().unwrap().unwrap();
+    assert!(accept);
+    for (f, value) in g.iter().zip(&last.values) {
+        assert_eq!(f.evaluate(&last.point), Some(*value));
+    }
+}
+
+#[test]
+fn test_misbehaving_peer(){
+    let g = parse_polynomial::<Fp97>("24*x0 + 15*x0*x1 + 35*x1").unwrap();
+
+    // the verifiers run whole protocols, a partial prover does not start one
+    let (mut p_chan, _v_chan) = tcp_pair();
+    let mut prover = sumcheck_ml::prover::Prover::new(g.clone()).unwrap().partial(1).unwrap();
+    assert!(matches!(prove_ml(&mut p_chan, &mut prover), Err(ProtocolError::Sumcheck(crate::error::Error::InvalidRoundCount(1, 2)))));
+
+    // a round message of the wrong length is rejected and the prover is told why
+    let (mut p_chan, mut v_chan) = tcp_pair();
+    let cheater = thread::spawn(move || {
+        p_chan.send(&Message::<Fp97>::Hello(Protocol::Ml, 2)).unwrap();
+        p_chan.send(&Message::Claim(Fp97::one())).unwrap();
+        p_chan.send(&Message::Round(vec![Fp97::one()])).unwrap();
+        p_chan.recv::<Fp97>().unwrap()
+    });
+    assert!(matches!(verify_ml(&mut v_chan, g.clone(), sumcheck_ml::verifier::VerifierOptions::default().allow_insecure(), &mut test_rng()), Err(ProtocolError::Transport(Error::InvalidRoundMessage(0, 1, 2)))));
+    assert!(matches!(cheater.join().unwrap(), Message::Abort(_)));
+
+    // a message inconsistent with the claim fails with the verifier's own error
+    let (mut p_chan, mut v_chan) = tcp_pair();
+    p_chan.send(&Message::<Fp97>::Hello(Protocol::Ml, 2)).unwrap();
+    p_chan.send(&Message::Claim(Fp97::one())).unwrap();
+    p_chan.send(&Message::Round(vec![Fp97::one(); 2])).unwrap();
+    let res = verify_ml(&mut v_chan, g.clone(), sumcheck_ml::verifier::VerifierOptions::default().allow_insecure(), &mut test_rng());
+    assert!(matches!(res, Err(ProtocolError::Sumcheck(crate::error::Error::ClaimMismatch(0, ..)))));
+
+    // a prover for another protocol
+    let (mut p_chan, mut v_chan) = tcp_pair();
+    p_chan.send(&Message::<Fp97>::Hello(Protocol::Pml, 2)).unwrap();
+    assert!(matches!(verify_ml(&mut v_chan, g.clone(), sumcheck_ml::verifier::VerifierOptions::default().allow_insecure(), &mut test_rng()), Err(ProtocolError::Transport(Error::ProtocolMismatch(Protocol::Ml, Protocol::Pml)))));
+
+    // the prover goes away mid-protocol
+    let (mut p_chan, mut v_chan) = tcp_pair();
+    p_chan.send(&Message::<Fp97>::Hello(Protocol::Ml, 2)).unwrap();
+    drop(p_chan);
+    assert!(matches!(verify_ml(&mut v_chan, g.clone(), sumcheck_ml::verifier::VerifierOptions::default().allow_insecure(), &mut test_rng()), Err(ProtocolError::Transport(Error::Disconnected))));
+
+    // a hello that cannot carry the number of variables is not sent at all
+    if let Ok(n) = usize::try_from(u64::from(u32::MAX) + 1) {
+        let (mut p_chan, _v_chan) = tcp_pair();
+        assert!(matches!(p_chan.send(&Message::<Fp97>::Hello(Protocol::Ml, n)), Err(Error::TooManyVars(m)) if m == n));
+    }
+
+    // the prover never answers
+    let (_p_chan, mut v_chan) = tcp_pair();
+    v_chan.set_timeout(Some(Duration::from_millis(50))).unwrap();
+    assert!(matches!(verify_ml(&mut v_chan, g, sumcheck_ml::verifier::VerifierOptions::default().allow_insecure(), &mut test_rng()), Err(ProtocolError::Transport(Error::Timeout))));
+}